@@ -0,0 +1,48 @@
+// This example shows how to shut down gracefully on ^C, while letting an
+// impatient user force quit by pressing ^C again within a few seconds.
+
+#[macro_use]
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{DoublePress, Signal};
+
+fn main() {
+    let int = chan_signal::notify_double(Signal::INT, Duration::from_secs(3));
+    let (sdone, rdone) = chan::sync(0);
+    thread::spawn(move || run(sdone));
+
+    println!("Working. Press ^C to shut down.");
+    let mut shutting_down = false;
+    loop {
+        chan_select! {
+            int.recv() -> press => match press.unwrap() {
+                DoublePress::First if !shutting_down => {
+                    println!("Shutting down. Press ^C again to force quit.");
+                    shutting_down = true;
+                    // A real program would tell its workers to stop here.
+                }
+                DoublePress::First => {
+                    println!("Still shutting down. Press ^C again to \
+                              force quit.");
+                }
+                DoublePress::Second => {
+                    println!("Forcing quit.");
+                    ::std::process::exit(130);
+                }
+            },
+            rdone.recv() => {
+                println!("Done.");
+                return;
+            }
+        }
+    }
+}
+
+fn run(_sdone: chan::Sender<()>) {
+    // Pretend to do a lot of work.
+    thread::sleep(Duration::from_secs(60));
+}
@@ -0,0 +1,57 @@
+// This example shows how to finish the work in progress before exiting on
+// ^C or TERM, while making sure the process still exits if that takes too
+// long.
+
+extern crate chan_signal;
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// The number of jobs in progress, and whether new jobs are still accepted.
+struct Jobs {
+    state: Mutex<(usize, bool)>,
+    cond: Condvar,
+}
+
+fn main() {
+    let jobs = Arc::new(Jobs {
+        state: Mutex::new((0, true)),
+        cond: Condvar::new(),
+    });
+    let drain_jobs = jobs.clone();
+    chan_signal::on_terminate_drain(Duration::from_secs(5), move || {
+        println!("Draining. No new jobs are accepted.");
+        let mut state = drain_jobs.state.lock().unwrap();
+        state.1 = false;
+        while state.0 > 0 {
+            println!("Waiting for {} job(s) to finish.", state.0);
+            state = drain_jobs.cond.wait(state).unwrap();
+        }
+        println!("Done.");
+    });
+
+    println!("Working. Press ^C to shut down.");
+    for n in 0.. {
+        {
+            let mut state = jobs.state.lock().unwrap();
+            if !state.1 {
+                break;
+            }
+            state.0 += 1;
+        }
+        let jobs = jobs.clone();
+        thread::spawn(move || {
+            println!("Job {} started.", n);
+            thread::sleep(Duration::from_secs(2));
+            println!("Job {} finished.", n);
+            jobs.state.lock().unwrap().0 -= 1;
+            jobs.cond.notify_all();
+        });
+        thread::sleep(Duration::from_millis(500));
+    }
+    // The process is terminated once the jobs have drained.
+    loop {
+        thread::park();
+    }
+}
@@ -0,0 +1,49 @@
+// This example shows how to print a status report when the user asks for
+// one by pressing ^T, like `dd` does on macOS and the BSDs. Press ^C to
+// quit.
+
+extern crate chan_signal;
+
+#[cfg(any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+))]
+fn main() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use chan_signal::Signal;
+
+    let signal = chan_signal::notify(&[Signal::INFO, Signal::INT]);
+    let done = Arc::new(AtomicUsize::new(0));
+    let work = done.clone();
+    thread::spawn(move || {
+        for _ in 0..1000 {
+            // Do some work.
+            thread::sleep(Duration::from_millis(100));
+            work.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    println!("Working. Press ^T for a status report, or ^C to quit.");
+    for sig in signal.iter() {
+        if sig == Signal::INFO {
+            println!("{} of 1000 items done", done.load(Ordering::SeqCst));
+        } else {
+            println!("Bye!");
+            return;
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+)))]
+fn main() {
+    println!("SIGINFO is only available on macOS and the BSDs.");
+}
@@ -40,7 +40,7 @@ fn main() {
     }
 }
 
-type Result<T> = ::std::result::Result<T, Box<Error+Send+Sync>>;
+type Result<T> = ::std::result::Result<T, Box<dyn Error+Send+Sync>>;
 
 fn run(signal: chan::Receiver<Signal>) -> Result<Vec<String>> {
     let lines = read_stdin_lines();
@@ -0,0 +1,33 @@
+// This example demonstrates reading signals from a `signalfd` instead of a
+// channel. In a real program, the file descriptor would be registered with
+// an event loop (e.g., `epoll`) alongside other descriptors. Here, we just
+// block on a `read` until a single INT signal arrives.
+
+extern crate chan_signal;
+extern crate libc;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn main() {
+    use std::mem;
+
+    use chan_signal::Signal;
+
+    let fd = chan_signal::signal_fd(&[Signal::INT]).unwrap();
+    println!("Send a INT signal my way!");
+
+    let mut info: libc::signalfd_siginfo = unsafe { mem::zeroed() };
+    let size = mem::size_of::<libc::signalfd_siginfo>();
+    let n = unsafe {
+        libc::read(fd, &mut info as *mut _ as *mut libc::c_void, size)
+    };
+    assert_eq!(n as usize, size);
+    assert_eq!(info.ssi_signo as libc::c_int, libc::SIGINT);
+    println!("Read signal {} from pid {}. Thanks :]",
+             info.ssi_signo, info.ssi_pid);
+    unsafe { libc::close(fd); }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn main() {
+    println!("signalfd is only available on Linux and Android.");
+}
@@ -1,4 +1,4 @@
-#[macro_use] extern crate chan;
+extern crate chan;
 extern crate chan_signal;
 
 use std::thread;
@@ -0,0 +1,15 @@
+// This example demonstrates receiving signals on a channel from the standard
+// library instead of one from the `chan` crate. It requests to be notified
+// about a SIGINT (usually ^C in your terminal) and blocks until it gets one.
+
+extern crate chan_signal;
+
+use chan_signal::{Signal, notify_std};
+
+fn main() {
+    let signal = notify_std(&[Signal::INT]);
+    println!("Send a INT signal my way!");
+    // block until we get a signal
+    assert_eq!(signal.recv(), Ok(Signal::INT));
+    println!("Thanks :]");
+}
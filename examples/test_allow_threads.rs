@@ -0,0 +1,72 @@
+extern crate chan_signal;
+#[cfg(feature = "log")]
+extern crate log;
+
+use std::env;
+use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
+
+use chan_signal::Signal;
+
+const WARNING: &str = "signals were first blocked";
+
+// Each case runs in a child process, since the warning is only printed the
+// first time signals are blocked.
+fn run(case: &str) -> String {
+    let exe = env::current_exe().unwrap();
+    let out = Command::new(exe)
+        .env("CHAN_SIGNAL_CASE", case)
+        .output().unwrap();
+    assert!(out.status.success());
+    String::from_utf8(out.stderr).unwrap()
+}
+
+// Print warnings to stderr, so that the parent process can look for them.
+#[cfg(feature = "log")]
+fn init_logger() {
+    struct Logger;
+
+    impl log::Log for Logger {
+        fn enabled(&self, _: &log::Metadata) -> bool { true }
+
+        fn log(&self, record: &log::Record) {
+            eprintln!("{}", record.args());
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: Logger = Logger;
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+}
+
+#[cfg(not(feature = "log"))]
+fn init_logger() {}
+
+fn main() {
+    match env::var("CHAN_SIGNAL_CASE") {
+        Ok(case) => {
+            init_logger();
+            if case == "allow" {
+                chan_signal::allow_existing_threads();
+            }
+            // Start a thread before any signals are blocked.
+            let (done, wait) = mpsc::channel::<()>();
+            let early = thread::spawn(move || { let _ = wait.recv(); });
+            let _r = chan_signal::notify(&[Signal::USR1]);
+            drop(done);
+            early.join().unwrap();
+        }
+        Err(_) => {
+            if !cfg!(any(target_os = "linux", target_os = "android")) {
+                return;
+            }
+            // Without the `log` feature, the warning goes straight to
+            // stderr.
+            assert!(run("warn").contains(WARNING));
+            assert!(!run("allow").contains(WARNING));
+        }
+    }
+}
@@ -0,0 +1,27 @@
+extern crate chan_signal;
+
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+// Only touched by the cleanup thread.
+static mut FIRST_RAN: bool = false;
+
+fn main() {
+    chan_signal::at_signal(&[Signal::USR1], || {
+        unsafe { FIRST_RAN = true; }
+        panic!("cleanup failed");
+    });
+    chan_signal::at_signal(&[Signal::USR1, Signal::USR2], || {
+        // Closures run in order, even if an earlier one panicked. Exit
+        // successfully instead of being killed by USR1.
+        if unsafe { FIRST_RAN } {
+            process::exit(0);
+        }
+    });
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_secs(1));
+    unreachable!();
+}
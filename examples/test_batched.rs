@@ -0,0 +1,37 @@
+extern crate chan_signal;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r = chan_signal::notify_batched(
+        &[Signal::USR1, Signal::USR2, Signal::WINCH]);
+    // Keep the watcher thread busy while the signals below arrive, so that
+    // they're all pending by the time it receives the first one.
+    let (busy_send, busy) = mpsc::channel();
+    chan_signal::on_signal(Signal::HUP, move |_| {
+        let _ = busy_send.send(());
+        thread::sleep(Duration::from_millis(200));
+    });
+
+    kill_this(Signal::HUP);
+    busy.recv().unwrap();
+    kill_this(Signal::WINCH);
+    kill_this(Signal::USR1);
+    kill_this(Signal::USR2);
+    let mut batch = r.recv().unwrap();
+    // The operating system doesn't record the order in which distinct
+    // signals became pending.
+    batch.sort();
+    assert_eq!(batch, vec![Signal::USR1, Signal::USR2, Signal::WINCH]);
+
+    // A signal that arrives on its own is a batch of one. `HUP` isn't
+    // subscribed, so it's left out of its batch, which is then not sent.
+    kill_this(Signal::HUP);
+    busy.recv().unwrap();
+    kill_this(Signal::USR2);
+    assert_eq!(r.recv(), Some(vec![Signal::USR2]));
+}
@@ -0,0 +1,56 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::mem;
+use std::ptr;
+
+fn current() -> libc::sigset_t {
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::pthread_sigmask(libc::SIG_BLOCK, ptr::null(), &mut set);
+        set
+    }
+}
+
+fn main() {
+    let baseline = unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::sigfillset(&mut set);
+        set
+    };
+    assert_eq!(unsafe { libc::sigismember(&current(), libc::SIGHUP) }, 0);
+
+    chan_signal::block_all().unwrap();
+    let mask = current();
+    for sig in 1..32 {
+        if sig == libc::SIGKILL || sig == libc::SIGSTOP {
+            // These can't be blocked.
+            assert_eq!(unsafe { libc::sigismember(&mask, sig) }, 0);
+            continue;
+        }
+        unsafe {
+            assert_eq!(
+                libc::sigismember(&mask, sig),
+                libc::sigismember(&baseline, sig),
+                "signal {}", sig);
+        }
+    }
+    realtime(&mask, &baseline);
+}
+
+// The C library may reserve a few signals below `SIGRTMIN` for itself, so
+// only the realtime signals it hands out are checked.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn realtime(mask: &libc::sigset_t, baseline: &libc::sigset_t) {
+    for sig in libc::SIGRTMIN()..libc::SIGRTMAX() + 1 {
+        unsafe {
+            assert_eq!(
+                libc::sigismember(mask, sig),
+                libc::sigismember(baseline, sig),
+                "signal {}", sig);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn realtime(_: &libc::sigset_t, _: &libc::sigset_t) {}
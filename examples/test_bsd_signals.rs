@@ -0,0 +1,30 @@
+extern crate chan_signal;
+
+#[cfg(any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+))]
+fn main() {
+    use chan_signal::{Signal, kill_this};
+
+    let r = chan_signal::notify(&[Signal::INFO, Signal::EMT]);
+    kill_this(Signal::INFO);
+    assert_eq!(r.recv(), Some(Signal::INFO));
+    kill_this(Signal::EMT);
+    assert_eq!(r.recv(), Some(Signal::EMT));
+
+    assert_eq!(Signal::INFO.name(), "SIGINFO");
+    assert_eq!(Signal::EMT.name(), "SIGEMT");
+    assert_eq!(Signal::INFO.stable_id(), 31);
+    assert_eq!(Signal::EMT.stable_id(), 32);
+    assert_eq!(Signal::from_stable_id(31), Some(Signal::INFO));
+    assert_eq!(Signal::from_stable_id(29), None);
+}
+
+#[cfg(not(any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+)))]
+fn main() {}
@@ -0,0 +1,47 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+// Only touched by the handler, which runs on one thread at a time.
+static mut CALLS: usize = 0;
+
+extern "C" fn handler(_: libc::c_int) {
+    unsafe { CALLS += 1; }
+}
+
+fn main() {
+    unsafe {
+        let handler = handler as *const () as libc::sighandler_t;
+        libc::signal(libc::SIGUSR1, handler);
+    }
+    let r = chan_signal::notify(&[Signal::USR1]);
+    chan_signal::chain_previous(&[Signal::USR1]).unwrap();
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+    // The previous handler is called right after the channel send.
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(unsafe { CALLS }, 1);
+
+    assert!(chan_signal::call_previous(Signal::USR1).unwrap());
+    assert_eq!(unsafe { CALLS }, 2);
+
+    // Nothing was installed for USR2 before it was blocked.
+    chan_signal::block(&[Signal::USR2]);
+    assert!(!chan_signal::call_previous(Signal::USR2).unwrap());
+    // Fault signals can never be chained.
+    assert!(chan_signal::chain_previous(&[Signal::SEGV]).is_err());
+
+    // When one signal can't be chained, the others aren't either, and their
+    // handlers aren't recorded.
+    unsafe {
+        libc::signal(libc::SIGHUP, handler as *const () as libc::sighandler_t);
+    }
+    let chain = chan_signal::chain_previous(&[Signal::HUP, Signal::SEGV]);
+    assert!(chain.is_err());
+    assert!(!chan_signal::call_previous(Signal::HUP).unwrap());
+    assert_eq!(unsafe { CALLS }, 2);
+}
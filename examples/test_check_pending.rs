@@ -0,0 +1,31 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::mem;
+use std::ptr;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    // Block the signals ourselves, so that no watcher thread is started to
+    // accept them.
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, libc::SIGUSR1);
+        libc::sigaddset(&mut set, libc::SIGUSR2);
+        libc::pthread_sigmask(libc::SIG_BLOCK, &set, ptr::null_mut());
+    }
+
+    // Nothing is pending yet.
+    let signals = [Signal::USR1, Signal::USR2];
+    assert_eq!(chan_signal::check_pending(&signals).unwrap(), None);
+
+    kill_this(Signal::USR1);
+    assert_eq!(chan_signal::check_pending(&[Signal::USR2]).unwrap(), None);
+    assert_eq!(
+        chan_signal::check_pending(&signals).unwrap(), Some(Signal::USR1));
+    // The signal was accepted, so it is no longer pending.
+    assert_eq!(chan_signal::check_pending(&signals).unwrap(), None);
+    assert!(chan_signal::pending().unwrap().is_empty());
+}
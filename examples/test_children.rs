@@ -0,0 +1,33 @@
+extern crate chan_signal;
+
+use std::collections::HashMap;
+use std::os::unix::process::ExitStatusExt;
+use std::process::Command;
+
+// The children are reaped by the watcher thread, not by `wait`.
+#[allow(clippy::zombie_processes)]
+fn main() {
+    let events = chan_signal::notify_children();
+
+    // Several children exiting at about the same time may coalesce into a
+    // single CHLD, but each one is still reported.
+    let mut codes = HashMap::new();
+    for code in 0..5 {
+        let child = Command::new("sh")
+            .arg("-c").arg(format!("exit {}", code))
+            .spawn().unwrap();
+        codes.insert(child.id() as i32, code);
+    }
+    let mut killed = Command::new("sleep").arg("60").spawn().unwrap();
+    killed.kill().unwrap();
+
+    for _ in 0..codes.len() + 1 {
+        let event = events.recv().unwrap();
+        if event.pid() == killed.id() as i32 {
+            assert_eq!(event.status().signal(), Some(9));
+        } else {
+            let code = codes.get(&event.pid()).cloned();
+            assert_eq!(event.status().code(), code);
+        }
+    }
+}
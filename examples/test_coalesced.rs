@@ -0,0 +1,37 @@
+extern crate chan_signal;
+
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn kill_and_wait(sig: Signal) {
+    kill_this(sig);
+    // Give the watcher a chance to receive the signal, so that the kernel
+    // doesn't coalesce them for us.
+    thread::sleep(Duration::from_millis(100));
+}
+
+fn main() {
+    let r = chan_signal::notify_coalesced(&[Signal::USR1, Signal::USR2]);
+    kill_and_wait(Signal::USR1);
+    kill_and_wait(Signal::USR1);
+    kill_and_wait(Signal::USR2);
+    kill_and_wait(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+    assert_eq!(r.recv(), Some(Signal::USR2));
+
+    // Once received, the same signal can be delivered again.
+    kill_and_wait(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+
+    // Coalescing happens in the watcher, so no thread is left behind for a
+    // receiver that is dropped.
+    if let Ok(tasks) = fs::read_dir("/proc/self/task") {
+        let before = tasks.count();
+        drop(chan_signal::notify_coalesced(&[Signal::USR2]));
+        let after = fs::read_dir("/proc/self/task").unwrap().count();
+        assert_eq!(after, before);
+    }
+}
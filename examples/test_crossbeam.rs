@@ -0,0 +1,31 @@
+extern crate chan_signal;
+#[cfg(feature = "crossbeam-channel")]
+extern crate crossbeam_channel;
+
+#[cfg(feature = "crossbeam-channel")]
+fn main() {
+    use chan_signal::{Signal, kill_this};
+
+    let (s, r) = crossbeam_channel::bounded(1);
+    chan_signal::notify_on_sink(s.clone(), Signal::USR1);
+    chan_signal::notify_on_sink(s, Signal::USR2);
+
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Ok(Signal::USR1));
+    kill_this(Signal::USR2);
+    assert_eq!(r.recv(), Ok(Signal::USR2));
+
+    // Once the receiver is gone, both senders are unsubscribed the next
+    // time their signal arrives.
+    drop(r);
+    let done = chan_signal::notify(&[Signal::USR1, Signal::USR2]);
+    kill_this(Signal::USR1);
+    assert_eq!(done.recv(), Some(Signal::USR1));
+    kill_this(Signal::USR2);
+    assert_eq!(done.recv(), Some(Signal::USR2));
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 1);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 1);
+}
+
+#[cfg(not(feature = "crossbeam-channel"))]
+fn main() {}
@@ -0,0 +1,21 @@
+extern crate chan_signal;
+
+use chan_signal::Signal;
+
+fn main() {
+    let dump = chan_signal::debug_dump();
+    assert!(dump.contains("watcher: not running"), "{}", dump);
+
+    let _r = chan_signal::notify(&[Signal::HUP, Signal::USR1]);
+    let _once = chan_signal::notify_once(Signal::USR2);
+    let _events = chan_signal::notify_events(&[Signal::TERM]);
+    chan_signal::notify_fn(&[Signal::WINCH], |_| {});
+
+    let dump = chan_signal::debug_dump();
+    assert!(dump.contains("watcher: running"));
+    assert!(dump.contains("channel {HUP, USR1}: 0 dropped"));
+    assert!(dump.contains("channel {USR2} (once): 0 dropped"));
+    assert!(dump.contains("event channel {TERM}: 0 dropped"));
+    assert!(dump.contains("callback 0: {WINCH}"));
+    assert!(dump.contains("blocked: {HUP, "));
+}
@@ -0,0 +1,50 @@
+extern crate chan_signal;
+
+use chan_signal::DefaultAction::*;
+use chan_signal::Signal;
+
+fn main() {
+    let table = [
+        (Signal::HUP, Term),
+        (Signal::INT, Term),
+        (Signal::QUIT, Core),
+        (Signal::ILL, Core),
+        (Signal::ABRT, Core),
+        (Signal::FPE, Core),
+        (Signal::KILL, Term),
+        (Signal::SEGV, Core),
+        (Signal::PIPE, Term),
+        (Signal::ALRM, Term),
+        (Signal::TERM, Term),
+        (Signal::USR1, Term),
+        (Signal::USR2, Term),
+        (Signal::CHLD, Ignore),
+        (Signal::CONT, Cont),
+        (Signal::STOP, Stop),
+        (Signal::TSTP, Stop),
+        (Signal::TTIN, Stop),
+        (Signal::TTOU, Stop),
+        (Signal::BUS, Core),
+        (Signal::PROF, Term),
+        (Signal::SYS, Core),
+        (Signal::TRAP, Core),
+        (Signal::URG, Ignore),
+        (Signal::VTALRM, Term),
+        (Signal::XCPU, Core),
+        (Signal::XFSZ, Core),
+        (Signal::WINCH, Ignore),
+    ];
+    for &(sig, action) in table.iter() {
+        assert_eq!(sig.default_action(), action, "{:?}", sig);
+    }
+    let poll = cfg!(any(
+        target_os = "linux", target_os = "android", target_os = "redox",
+        target_os = "fuchsia", target_os = "haiku",
+        target_os = "solaris", target_os = "illumos"
+    ));
+    if poll {
+        assert_eq!(Signal::IO.default_action(), Term);
+    } else {
+        assert_eq!(Signal::IO.default_action(), Ignore);
+    }
+}
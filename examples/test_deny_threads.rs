@@ -0,0 +1,33 @@
+extern crate chan_signal;
+
+use std::sync::mpsc;
+use std::thread;
+
+use chan_signal::{Signal, SignalError, kill_this};
+
+fn main() {
+    if !cfg!(any(target_os = "linux", target_os = "android")) {
+        return;
+    }
+    chan_signal::deny_existing_threads();
+
+    // Start a thread before any signals are blocked, which is a mistake.
+    let (done, wait) = mpsc::channel::<()>();
+    let early = thread::spawn(move || { let _ = wait.recv(); });
+    let err = chan_signal::try_notify(&[Signal::USR1]).unwrap_err();
+    match err.get_ref().and_then(|e| e.downcast_ref::<SignalError>()) {
+        Some(&SignalError::ThreadsAlreadySpawned(2)) => {}
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(chan_signal::try_block(&[Signal::USR2]).is_err());
+    assert!(!chan_signal::is_blocked(Signal::USR1).unwrap());
+    assert_eq!(chan_signal::thread_count_at_init(), None);
+
+    // Once the other thread is gone, signals can be blocked.
+    drop(done);
+    early.join().unwrap();
+    let r = chan_signal::try_notify(&[Signal::USR1]).unwrap();
+    assert_eq!(chan_signal::thread_count_at_init(), Some(1));
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+}
@@ -0,0 +1,25 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{DoublePress, Signal, kill_this};
+
+fn main() {
+    let window = Duration::from_millis(500);
+    let r = chan_signal::notify_double(Signal::USR1, window);
+    let press = || {
+        kill_this(Signal::USR1);
+        r.recv().unwrap()
+    };
+
+    assert_eq!(press(), DoublePress::First);
+    assert_eq!(press(), DoublePress::Second);
+    // A third press starts over.
+    assert_eq!(press(), DoublePress::First);
+
+    // So does a press after the window has passed.
+    thread::sleep(window * 2);
+    assert_eq!(press(), DoublePress::First);
+    assert_eq!(press(), DoublePress::Second);
+}
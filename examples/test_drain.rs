@@ -0,0 +1,51 @@
+extern crate chan_signal;
+
+use std::env;
+use std::os::unix::process::ExitStatusExt;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chan_signal::{Signal, kill_this};
+
+// Each case runs in a child process, since it ends by terminating the
+// process with TERM.
+fn run(case: &str) -> (Option<i32>, String, Duration) {
+    let start = Instant::now();
+    let out = Command::new(env::current_exe().unwrap())
+        .env("CHAN_SIGNAL_CASE", case)
+        .output().unwrap();
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    (out.status.signal(), stdout, start.elapsed())
+}
+
+fn child(case: &str) {
+    let timeout = Duration::from_millis(500);
+    if case == "fast" {
+        chan_signal::on_terminate_drain(timeout, || println!("drained"));
+    } else {
+        chan_signal::on_terminate_drain(timeout, || loop {
+            thread::sleep(Duration::from_secs(60));
+        });
+    }
+    kill_this(Signal::TERM);
+    loop {
+        thread::park();
+    }
+}
+
+fn main() {
+    if let Ok(case) = env::var("CHAN_SIGNAL_CASE") {
+        return child(&case);
+    }
+
+    let (signal, stdout, elapsed) = run("fast");
+    assert_eq!(signal, Some(i32::from(Signal::TERM)));
+    assert_eq!(stdout, "drained\n");
+    assert!(elapsed < Duration::from_millis(500), "{:?}", elapsed);
+
+    let (signal, stdout, elapsed) = run("never");
+    assert_eq!(signal, Some(i32::from(Signal::TERM)));
+    assert_eq!(stdout, "");
+    assert!(elapsed >= Duration::from_millis(500), "{:?}", elapsed);
+}
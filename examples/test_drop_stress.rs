@@ -0,0 +1,40 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+const SENT: u64 = 50;
+
+fn main() {
+    // Nobody ever receives from `slow` after its buffer fills up, while
+    // `fast` is drained by another thread.
+    let (slow, _slow_r) = chan::sync(2);
+    let (fast, fast_r) = chan::sync(0);
+    chan_signal::notify_on(&slow, Signal::USR1);
+    chan_signal::notify_on(&fast, Signal::USR1);
+    let drain = thread::spawn(move || {
+        let mut n = 0;
+        while n < SENT {
+            fast_r.recv().unwrap();
+            n += 1;
+        }
+    });
+    // Give the draining thread time to wait on `fast`, since a channel
+    // without a buffer only takes a signal while someone is receiving.
+    thread::sleep(Duration::from_millis(100));
+    for _ in 0..SENT {
+        kill_this(Signal::USR1);
+        thread::sleep(Duration::from_millis(10));
+    }
+    drain.join().unwrap();
+
+    let stats = chan_signal::stats().get(Signal::USR1);
+    assert_eq!(stats.received(), SENT);
+    assert_eq!(chan_signal::dropped_count(&slow), Some(SENT - 2));
+    assert_eq!(chan_signal::dropped_count(&fast), Some(0));
+    assert_eq!(stats.dropped(), SENT - 2);
+    assert_eq!(stats.delivered(), SENT + 2);
+}
@@ -0,0 +1,34 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let (s, r) = chan::sync(1);
+    chan_signal::notify_on(&s, Signal::USR1);
+    assert_eq!(chan_signal::dropped_count(&s), Some(0));
+    for _ in 0..3 {
+        kill_this(Signal::USR1);
+        thread::sleep(Duration::from_millis(100));
+    }
+    // The first signal fills the buffer, so the other two are dropped.
+    assert_eq!(chan_signal::dropped_count(&s), Some(2));
+    assert_eq!(r.recv(), Some(Signal::USR1));
+
+    let (other, other_r) = chan::sync::<Signal>(1);
+    assert_eq!(chan_signal::dropped_count(&other), None);
+    assert_eq!(chan_signal::dropped_count(&other_r), None);
+
+    // The channels created by `notify` can be looked up by their receiver.
+    let r = chan_signal::notify(&[Signal::USR2]);
+    assert_eq!(chan_signal::dropped_count(&r), Some(0));
+    for _ in 0..102 {
+        kill_this(Signal::USR2);
+        thread::sleep(Duration::from_millis(10));
+    }
+    // Its buffer holds 100 signals.
+    assert_eq!(chan_signal::dropped_count(&r), Some(2));
+}
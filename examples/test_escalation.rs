@@ -0,0 +1,22 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r = chan_signal::notify_with_escalation(&[Signal::INT]);
+    kill_this(Signal::INT);
+    assert_eq!(r.recv(), Some(Signal::INT));
+
+    // After a reset, the next one is delivered as usual.
+    chan_signal::reset_escalation(&[Signal::INT]);
+    kill_this(Signal::INT);
+    assert_eq!(r.recv(), Some(Signal::INT));
+
+    // The following will terminate the process.
+    kill_this(Signal::INT);
+    thread::sleep(Duration::from_secs(1));
+    unreachable!();
+}
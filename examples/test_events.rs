@@ -0,0 +1,27 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let events = chan_signal::notify_events(&[Signal::USR1, Signal::USR2]);
+    let before = Instant::now();
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(50));
+    kill_this(Signal::USR2);
+    thread::sleep(Duration::from_millis(50));
+
+    let first = events.recv().unwrap();
+    let second = events.recv().unwrap();
+    assert_eq!(first.signal(), Signal::USR1);
+    assert_eq!(second.signal(), Signal::USR2);
+    assert_eq!(first.value(), None);
+    // Every delivery gets the next number, so there's no gap.
+    assert_eq!(second.seq(), first.seq() + 1);
+    // The time is when the signal arrived, not when it was received.
+    assert!(before <= first.received_at());
+    assert!(first.received_at() < second.received_at());
+    assert!(second.received_at() + Duration::from_millis(25) < Instant::now());
+}
@@ -0,0 +1,18 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    // The watcher is woken up with `URG`, so it can't be excluded.
+    assert!(chan_signal::set_excluded(&[Signal::PROF, Signal::URG]).is_err());
+
+    // Nothing was excluded by the failed call.
+    assert!(chan_signal::try_notify(&[Signal::PROF]).is_ok());
+
+    let r = chan_signal::notify(&[Signal::USR1]);
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+    // This would never return if the watcher didn't wait on `URG`.
+    chan_signal::shutdown().unwrap();
+    assert!(!chan_signal::watcher_alive());
+}
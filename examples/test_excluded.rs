@@ -0,0 +1,27 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, SignalError, kill_this};
+
+fn main() {
+    chan_signal::set_excluded(&[Signal::PROF, Signal::VTALRM]).unwrap();
+
+    let err = chan_signal::try_notify(&[Signal::PROF]).unwrap_err();
+    match err.get_ref().and_then(|e| e.downcast_ref::<SignalError>()) {
+        Some(&SignalError::Excluded(Signal::PROF)) => {}
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(chan_signal::try_block(&[Signal::VTALRM]).is_err());
+
+    // Neither blocking everything nor starting the watcher thread blocks
+    // the excluded signals.
+    let r = chan_signal::notify(&[Signal::USR1]);
+    chan_signal::block_all().unwrap();
+    assert!(chan_signal::is_blocked(Signal::TERM).unwrap());
+    assert!(!chan_signal::is_blocked(Signal::PROF).unwrap());
+    assert!(!chan_signal::is_blocked(Signal::VTALRM).unwrap());
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+
+    // It's too late to change the excluded signals now.
+    assert!(chan_signal::set_excluded(&[Signal::PROF]).is_err());
+}
@@ -0,0 +1,12 @@
+extern crate chan_signal;
+extern crate libc;
+
+use chan_signal::Signal;
+
+fn main() {
+    assert_eq!(Signal::INT.exit_code(), 128 + libc::SIGINT);
+    assert_eq!(Signal::TERM.exit_code(), 128 + libc::SIGTERM);
+    for sig in Signal::iter() {
+        assert_eq!(sig.exit_code(), 128 + sig.as_raw());
+    }
+}
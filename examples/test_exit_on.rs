@@ -0,0 +1,24 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    // Cancelling unsubscribes, so USR2 may be ignored afterwards.
+    let guard = chan_signal::exit_on(&[Signal::USR2], |_| 1);
+    assert!(chan_signal::ignore(&[Signal::USR2]).is_err());
+    drop(guard);
+    chan_signal::ignore(&[Signal::USR2]).unwrap();
+
+    // Other subscribers still receive the signal.
+    let r = chan_signal::notify(&[Signal::USR1]);
+    let _guard = chan_signal::exit_on(&[Signal::USR1], |_| 42);
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+
+    // The process exits with status 42 shortly after the send.
+    thread::sleep(Duration::from_secs(1));
+    unreachable!();
+}
@@ -0,0 +1,40 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let (sfast, rfast) = chan::sync(10);
+    let (sslow, rslow) = chan::sync(1);
+    chan_signal::notify_on(&sfast, Signal::HUP);
+    chan_signal::notify_on(&sslow, Signal::HUP);
+    let done = chan_signal::notify(&[Signal::USR1]);
+    chan_signal::set_fan_out(Signal::HUP, 100);
+
+    // The first signal fills up the slow channel, so the second one is
+    // retried until its receiver catches up.
+    kill_this(Signal::HUP);
+    assert_eq!(rfast.recv(), Some(Signal::HUP));
+    kill_this(Signal::HUP);
+    assert_eq!(rfast.recv(), Some(Signal::HUP));
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(rslow.recv(), Some(Signal::HUP));
+    assert_eq!(rslow.recv(), Some(Signal::HUP));
+    kill_this(Signal::USR1);
+    assert_eq!(done.recv(), Some(Signal::USR1));
+    assert_eq!(chan_signal::dropped_count(&sslow), Some(0));
+
+    // Without retries, the slow receiver misses the second signal.
+    chan_signal::set_fan_out(Signal::HUP, 0);
+    kill_this(Signal::HUP);
+    assert_eq!(rfast.recv(), Some(Signal::HUP));
+    kill_this(Signal::HUP);
+    assert_eq!(rfast.recv(), Some(Signal::HUP));
+    kill_this(Signal::USR1);
+    assert_eq!(done.recv(), Some(Signal::USR1));
+    assert_eq!(chan_signal::dropped_count(&sslow), Some(1));
+    assert_eq!(rslow.recv(), Some(Signal::HUP));
+}
@@ -0,0 +1,25 @@
+extern crate chan_signal;
+
+use chan_signal::Signal;
+
+fn main() {
+    let faults: Vec<Signal> =
+        Signal::iter().filter(|s| s.is_fault()).collect();
+    assert_eq!(
+        faults,
+        vec![Signal::ILL, Signal::FPE, Signal::SEGV, Signal::BUS]);
+
+    for &sig in &faults {
+        let err = chan_signal::try_notify(&[sig]).unwrap_err();
+        assert!(err.to_string().contains(&sig.to_string()), "{}", err);
+        assert_eq!(chan_signal::subscriber_count(sig), 0);
+    }
+
+    // Blocking every subscribable signal leaves the fault signals alone,
+    // so that, e.g., stack overflows are still reported.
+    chan_signal::block_all_subscribable();
+    assert!(chan_signal::is_blocked(Signal::TERM).unwrap());
+    for &sig in &faults {
+        assert!(!chan_signal::is_blocked(sig).unwrap(), "{}", sig);
+    }
+}
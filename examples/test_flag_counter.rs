@@ -0,0 +1,41 @@
+extern crate chan_signal;
+
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let chan = chan_signal::notify(&[Signal::USR1]);
+    let flag = chan_signal::notify_flag(&[Signal::USR1, Signal::USR2]);
+    let counter = chan_signal::notify_counter(Signal::USR1);
+    assert!(!flag.load(Ordering::Relaxed));
+    assert_eq!(counter.load(Ordering::Relaxed), 0);
+
+    kill_this(Signal::USR1);
+    assert_eq!(chan.recv(), Some(Signal::USR1));
+    thread::sleep(Duration::from_millis(100));
+    assert!(flag.load(Ordering::Relaxed));
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+    kill_this(Signal::USR1);
+    assert_eq!(chan.recv(), Some(Signal::USR1));
+    thread::sleep(Duration::from_millis(100));
+    assert!(flag.load(Ordering::Relaxed));
+    assert_eq!(counter.load(Ordering::Relaxed), 2);
+
+    // The counter saturates.
+    counter.store(usize::MAX, Ordering::SeqCst);
+    kill_this(Signal::USR1);
+    assert_eq!(chan.recv(), Some(Signal::USR1));
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(counter.load(Ordering::Relaxed), usize::MAX);
+
+    // Dropping the flag unsubscribes it once a signal arrives.
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 1);
+    drop(flag);
+    kill_this(Signal::USR2);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 0);
+}
@@ -0,0 +1,26 @@
+extern crate chan_signal;
+extern crate libc;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let usr1 = chan_signal::notify(&[Signal::USR1]);
+    let usr2 = chan_signal::notify(&[Signal::USR2]);
+    let me = unsafe { libc::getpid() };
+
+    // Relay every USR1 as a USR2.
+    kill_this(Signal::USR1);
+    let sig = usr1.recv().unwrap();
+    assert_eq!(sig, Signal::USR1);
+    let results = chan_signal::forward_to(Signal::USR2, &[me]);
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_ok());
+    assert_eq!(usr2.recv(), Some(Signal::USR2));
+
+    // A failure for one pid doesn't stop the others.
+    let pids = [i32::MAX, me];
+    let results = chan_signal::forward_to(Signal::USR1, &pids);
+    assert!(results[0].is_err());
+    assert!(results[1].is_ok());
+    assert_eq!(usr1.recv(), Some(Signal::USR1));
+}
@@ -0,0 +1,26 @@
+extern crate chan_signal;
+
+use std::thread;
+
+use chan_signal::{GracefulShutdown, Signal, kill_this};
+
+fn main() {
+    let shutdown = GracefulShutdown::new(&[Signal::USR1, Signal::USR2]);
+    let token = shutdown.token();
+    assert!(!token.is_triggered());
+    assert_eq!(token.triggering_signal(), None);
+
+    let workers: Vec<_> = (0..4).map(|_| {
+        let token = shutdown.token();
+        thread::spawn(move || token.wait())
+    }).collect();
+    kill_this(Signal::USR1);
+    for worker in workers {
+        assert_eq!(worker.join().unwrap(), Signal::USR1);
+    }
+
+    // Only the first signal counts, and later tokens see it too.
+    kill_this(Signal::USR2);
+    assert_eq!(shutdown.token().wait(), Signal::USR1);
+    assert!(token.is_triggered());
+}
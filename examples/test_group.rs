@@ -0,0 +1,17 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let reload = chan_signal::notify_group("reload", &[Signal::USR1]);
+    let stats = chan_signal::notify_group(
+        "stats", &[Signal::USR1, Signal::USR2]);
+
+    kill_this(Signal::USR1);
+    assert_eq!(reload.recv(), Some(("reload".to_string(), Signal::USR1)));
+    assert_eq!(stats.recv(), Some(("stats".to_string(), Signal::USR1)));
+
+    kill_this(Signal::USR2);
+    assert_eq!(stats.recv(), Some(("stats".to_string(), Signal::USR2)));
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 2);
+}
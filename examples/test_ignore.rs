@@ -0,0 +1,16 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let _r = chan_signal::notify(&[Signal::USR1]);
+    // A subscribed signal cannot be ignored.
+    assert!(chan_signal::ignore(&[Signal::HUP, Signal::USR1]).is_err());
+    // KILL can never be ignored.
+    assert!(chan_signal::ignore(&[Signal::KILL]).is_err());
+
+    // If HUP or PIPE weren't ignored, this would terminate the process.
+    chan_signal::ignore(&[Signal::HUP, Signal::PIPE]).unwrap();
+    kill_this(Signal::HUP);
+    kill_this(Signal::PIPE);
+}
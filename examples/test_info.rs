@@ -0,0 +1,21 @@
+extern crate chan_signal;
+extern crate libc;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r = chan_signal::notify_info(&[Signal::USR1]);
+    kill_this(Signal::USR1);
+    let info = r.recv().unwrap();
+    assert_eq!(info.signal(), Signal::USR1);
+    assert!(!info.is_from_kernel());
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        assert_eq!(info.code(), libc::SI_USER);
+        assert_eq!(info.pid(), Some(unsafe { libc::getpid() }));
+        assert_eq!(info.uid(), Some(unsafe { libc::getuid() }));
+    } else {
+        assert_eq!(info.pid(), None);
+        assert_eq!(info.uid(), None);
+    }
+    assert_eq!(info.value(), None);
+}
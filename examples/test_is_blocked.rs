@@ -0,0 +1,27 @@
+extern crate chan_signal;
+
+use std::thread;
+
+use chan_signal::Signal;
+
+fn main() {
+    let early = thread::spawn(|| {
+        thread::park();
+        chan_signal::is_blocked(Signal::USR1).unwrap()
+    });
+
+    assert!(!chan_signal::is_blocked(Signal::USR1).unwrap());
+    chan_signal::try_block(&[Signal::USR1]).unwrap();
+    assert!(chan_signal::is_blocked(Signal::USR1).unwrap());
+    assert!(!chan_signal::is_blocked(Signal::USR2).unwrap());
+
+    // Threads spawned afterwards inherit the mask, but earlier ones don't.
+    let late = thread::spawn(|| {
+        chan_signal::is_blocked(Signal::USR1).unwrap()
+    });
+    assert!(late.join().unwrap());
+    early.thread().unpark();
+    assert!(!early.join().unwrap());
+
+    assert!(chan_signal::is_blocked(Signal::Other(1000)).is_err());
+}
@@ -0,0 +1,26 @@
+extern crate chan_signal;
+
+use std::convert::TryFrom;
+
+use chan_signal::{Signal, SignalSet};
+
+fn main() {
+    let all: SignalSet = Signal::iter().collect();
+    assert_eq!(all.len(), Signal::ALL.len());
+    // `PWR` and `STKFLT` only exist on Linux and Android, and `INFO` and
+    // `EMT` only on macOS and the BSDs.
+    let extra = cfg!(any(
+        target_os = "linux", target_os = "android",
+        target_os = "macos", target_os = "ios",
+        target_os = "freebsd", target_os = "dragonfly",
+        target_os = "netbsd", target_os = "openbsd",
+    ));
+    assert_eq!(Signal::iter().count(), if extra { 31 } else { 29 });
+    for sig in Signal::iter() {
+        assert_eq!(Signal::try_from(sig.as_raw()), Ok(sig));
+    }
+
+    let uncatchable: Vec<Signal> =
+        Signal::iter().filter(|sig| !sig.is_catchable()).collect();
+    assert_eq!(uncatchable, vec![Signal::KILL, Signal::STOP]);
+}
@@ -0,0 +1,21 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let job_control: Vec<Signal> =
+        Signal::iter().filter(|sig| sig.is_job_control()).collect();
+    assert_eq!(job_control.len(), 5);
+    for &sig in &job_control {
+        assert!(chan_signal::SignalSet::job_control().contains(sig));
+    }
+    assert!(Signal::STOP.is_job_control());
+    assert!(!Signal::INT.is_job_control());
+    assert!(!Signal::CHLD.is_job_control());
+
+    let r = chan_signal::notify_job_control();
+    assert_eq!(chan_signal::subscriber_count(Signal::STOP), 0);
+    // Since it's blocked, this doesn't stop the process.
+    kill_this(Signal::TSTP);
+    assert_eq!(r.recv(), Some(Signal::TSTP));
+}
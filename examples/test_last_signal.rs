@@ -0,0 +1,24 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    assert_eq!(chan_signal::last_signal(), None);
+
+    // The channel has no buffer and nobody receives from it, so every
+    // signal is dropped. It's still recorded.
+    let (s, _r) = chan::sync(0);
+    chan_signal::notify_on(&s, Signal::USR1);
+    chan_signal::notify_on(&s, Signal::USR2);
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(chan_signal::last_signal(), Some(Signal::USR1));
+
+    kill_this(Signal::USR2);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(chan_signal::last_signal(), Some(Signal::USR2));
+}
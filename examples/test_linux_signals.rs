@@ -0,0 +1,24 @@
+extern crate chan_signal;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn main() {
+    use chan_signal::{Signal, kill_this};
+
+    let r = chan_signal::notify(&[Signal::PWR, Signal::STKFLT]);
+    kill_this(Signal::PWR);
+    assert_eq!(r.recv(), Some(Signal::PWR));
+    kill_this(Signal::STKFLT);
+    assert_eq!(r.recv(), Some(Signal::STKFLT));
+
+    assert_eq!(Signal::PWR.name(), "SIGPWR");
+    assert_eq!(Signal::STKFLT.name(), "SIGSTKFLT");
+    assert_eq!(Signal::PWR.stable_id(), 29);
+    assert_eq!(Signal::STKFLT.stable_id(), 30);
+    assert_eq!(Signal::from_stable_id(29), Some(Signal::PWR));
+    assert_eq!(Signal::from_stable_id(31), None);
+    assert!(Signal::ALL.contains(&Signal::PWR));
+    assert!(Signal::ALL.contains(&Signal::STKFLT));
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn main() {}
@@ -0,0 +1,66 @@
+extern crate chan_signal;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate lazy_static;
+#[cfg(feature = "log")]
+extern crate log;
+
+#[cfg(feature = "log")]
+mod logger {
+    use std::sync::Mutex;
+
+    use log::{Log, Metadata, Record};
+
+    lazy_static! {
+        pub static ref LOGGED: Mutex<Vec<String>> = Mutex::new(vec![]);
+    }
+
+    pub struct Logger;
+
+    impl Log for Logger {
+        fn enabled(&self, _: &Metadata) -> bool { true }
+
+        fn log(&self, record: &Record) {
+            LOGGED.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+}
+
+#[cfg(feature = "log")]
+fn main() {
+    use std::thread;
+    use std::time::Duration;
+
+    use chan_signal::{Signal, kill_this};
+    use logger::{LOGGED, Logger};
+
+    static LOGGER: Logger = Logger;
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let r = chan_signal::notify(&[Signal::USR1]);
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+
+    // The watcher logs the delivery right after the send completes.
+    let wanted = [
+        "watcher thread started",
+        "(SIGUSR1)",
+        "delivered SIGUSR1 to channel 0",
+    ];
+    let logged = |w: &str| {
+        LOGGED.lock().unwrap().iter().any(|l| l.ends_with(w))
+    };
+    for _ in 0..100 {
+        if wanted.iter().all(|w| logged(w)) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("missing log lines, got: {:?}", *LOGGED.lock().unwrap());
+}
+
+#[cfg(not(feature = "log"))]
+fn main() {}
@@ -0,0 +1,61 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::mem;
+use std::ptr;
+
+use chan_signal::{Signal, kill_this};
+
+// Read the mask with libc's own `sigset_t`, so that a mismatch with the
+// layout this crate uses shows up as the wrong signals being blocked.
+fn blocked() -> Vec<libc::c_int> {
+    let mut set: libc::sigset_t = unsafe { mem::zeroed() };
+    let ecode = unsafe {
+        libc::pthread_sigmask(libc::SIG_BLOCK, ptr::null(), &mut set)
+    };
+    assert_eq!(ecode, 0);
+    (1..highest() + 1)
+        .filter(|&sig| unsafe { libc::sigismember(&set, sig) } == 1)
+        .collect()
+}
+
+// Realtime signals live past the first 32 bits of the set, so the last one
+// is blocked too where there are any.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn highest() -> libc::c_int {
+    libc::SIGRTMAX()
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn last_realtime() -> Option<(Signal, libc::c_int)> {
+    let offset = libc::SIGRTMAX() - libc::SIGRTMIN();
+    Some((Signal::rt(offset as u8).unwrap(), libc::SIGRTMAX()))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn highest() -> libc::c_int {
+    31
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn last_realtime() -> Option<(Signal, libc::c_int)> {
+    None
+}
+
+fn main() {
+    assert_eq!(blocked(), vec![]);
+
+    let mut signals = vec![Signal::USR1, Signal::WINCH];
+    let mut want = vec![libc::SIGUSR1, libc::SIGWINCH];
+    if let Some((signal, sig)) = last_realtime() {
+        signals.push(signal);
+        want.push(sig);
+    }
+    want.sort();
+    chan_signal::block(signals);
+    assert_eq!(blocked(), want);
+
+    let r = chan_signal::notify(&[Signal::USR1]);
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+}
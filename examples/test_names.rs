@@ -0,0 +1,48 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, SignalSet};
+
+fn main() {
+    let table = [
+        (Signal::HUP, "SIGHUP", "Hangup"),
+        (Signal::INT, "SIGINT", "Interrupt"),
+        (Signal::QUIT, "SIGQUIT", "Quit"),
+        (Signal::ILL, "SIGILL", "Illegal instruction"),
+        (Signal::ABRT, "SIGABRT", "Aborted"),
+        (Signal::FPE, "SIGFPE", "Floating point exception"),
+        (Signal::KILL, "SIGKILL", "Killed"),
+        (Signal::SEGV, "SIGSEGV", "Segmentation fault"),
+        (Signal::PIPE, "SIGPIPE", "Broken pipe"),
+        (Signal::ALRM, "SIGALRM", "Alarm clock"),
+        (Signal::TERM, "SIGTERM", "Terminated"),
+        (Signal::USR1, "SIGUSR1", "User defined signal 1"),
+        (Signal::USR2, "SIGUSR2", "User defined signal 2"),
+        (Signal::CHLD, "SIGCHLD", "Child exited"),
+        (Signal::CONT, "SIGCONT", "Continued"),
+        (Signal::STOP, "SIGSTOP", "Stopped (signal)"),
+        (Signal::TSTP, "SIGTSTP", "Stopped"),
+        (Signal::TTIN, "SIGTTIN", "Stopped (tty input)"),
+        (Signal::TTOU, "SIGTTOU", "Stopped (tty output)"),
+        (Signal::BUS, "SIGBUS", "Bus error"),
+        (Signal::PROF, "SIGPROF", "Profiling timer expired"),
+        (Signal::SYS, "SIGSYS", "Bad system call"),
+        (Signal::TRAP, "SIGTRAP", "Trace/breakpoint trap"),
+        (Signal::URG, "SIGURG", "Urgent I/O condition"),
+        (Signal::VTALRM, "SIGVTALRM", "Virtual timer expired"),
+        (Signal::XCPU, "SIGXCPU", "CPU time limit exceeded"),
+        (Signal::XFSZ, "SIGXFSZ", "File size limit exceeded"),
+        (Signal::IO, "SIGIO", "I/O possible"),
+        (Signal::WINCH, "SIGWINCH", "Window changed"),
+    ];
+    for &(sig, name, description) in table.iter() {
+        assert_eq!(sig.name(), name);
+        assert_eq!(sig.to_string(), name);
+        assert_eq!(sig.name(), format!("SIG{:?}", sig));
+        assert_eq!(sig.description(), description);
+    }
+    for sig in &SignalSet::all() {
+        assert!(!sig.description().is_empty(), "{:?}", sig);
+    }
+    assert!(!Signal::RT(0).description().is_empty());
+    assert!(!Signal::Other(1000).description().is_empty());
+}
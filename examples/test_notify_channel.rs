@@ -0,0 +1,18 @@
+extern crate chan_signal;
+
+use std::thread;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let (s, r) = chan_signal::notify_channel(&[Signal::USR1]);
+
+    let s2 = s.clone();
+    thread::spawn(move || s2.send(Signal::TERM)).join().unwrap();
+    assert_eq!(r.recv(), Some(Signal::TERM));
+
+    // Dropping our senders keeps the subscription alive.
+    drop(s);
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+}
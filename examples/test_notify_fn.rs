@@ -0,0 +1,65 @@
+extern crate chan_signal;
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let _keep = chan_signal::notify(&[Signal::USR1, Signal::USR2]);
+    let count = Arc::new(AtomicUsize::new(0));
+    let thread_count = count.clone();
+    let mut seen = vec![];
+    let id = chan_signal::notify_fn(&[Signal::USR1], move |sig| {
+        seen.push(sig);
+        thread_count.store(seen.len(), Ordering::SeqCst);
+    });
+    // A panicking callback is removed without taking the watcher with it.
+    chan_signal::notify_fn(&[Signal::USR2], |_| panic!("boom"));
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 2);
+
+    kill_this(Signal::USR2);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 1);
+
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+
+    chan_signal::remove_callback(id);
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    // Removing it twice does nothing.
+    chan_signal::remove_callback(id);
+
+    // A callback may remove itself and other callbacks, and dump the
+    // registry, without deadlocking the watcher. The one it removes isn't
+    // called afterwards.
+    let calls = Arc::new(AtomicUsize::new(0));
+    let ids = Arc::new(Mutex::new(vec![]));
+    let (thread_calls, thread_ids) = (calls.clone(), ids.clone());
+    let first = chan_signal::notify_fn(&[Signal::USR1], move |_| {
+        assert!(!chan_signal::debug_dump().is_empty());
+        thread_calls.fetch_add(1, Ordering::SeqCst);
+        for &id in thread_ids.lock().unwrap().iter() {
+            chan_signal::remove_callback(id);
+        }
+    });
+    let thread_calls = calls.clone();
+    let second = chan_signal::notify_fn(&[Signal::USR1], move |_| {
+        thread_calls.fetch_add(1, Ordering::SeqCst);
+    });
+    ids.lock().unwrap().extend(vec![first, second]);
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 1);
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,31 @@
+#[macro_use]
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let (s1, r1) = chan::sync(3);
+    chan_signal::notify_on_many(&s1, &[Signal::USR1, Signal::USR2]);
+    let (s2, r2) = chan::sync(3);
+    chan_signal::notify_on(&s2, Signal::USR1);
+    chan_signal::notify_on(&s2, Signal::USR2);
+
+    for &sig in &[Signal::USR1, Signal::USR2] {
+        kill_this(sig);
+        assert_eq!(r1.recv(), Some(sig));
+        assert_eq!(r2.recv(), Some(sig));
+    }
+    // Neither channel was subscribed to anything else.
+    chan_signal::block(&[Signal::HUP]);
+    kill_this(Signal::HUP);
+    thread::sleep(Duration::from_millis(100));
+    chan_select! {
+        default => {},
+        r1.recv() => panic!("unexpected signal on r1"),
+        r2.recv() => panic!("unexpected signal on r2"),
+    }
+}
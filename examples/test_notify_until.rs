@@ -0,0 +1,62 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chan_signal::{Signal, kill_this};
+
+extern "C" fn interrupt(_: libc::c_int) {}
+
+fn main() {
+    // A deadline in the past doesn't block, and returns the signal that is
+    // already pending (nothing else is running to receive it yet)...
+    chan_signal::block(&[Signal::USR2]);
+    kill_this(Signal::USR2);
+    let sig = chan_signal::notify_until(&[Signal::USR2], Instant::now());
+    assert_eq!(sig, Some(Signal::USR2));
+    // ...or nothing if no signal is.
+    let start = Instant::now();
+    assert_eq!(chan_signal::notify_until(&[Signal::USR2], start), None);
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    // A near deadline with no signal times out at the deadline.
+    let deadline = Instant::now() + Duration::from_millis(100);
+    assert_eq!(chan_signal::notify_until(&[Signal::WINCH], deadline), None);
+    assert!(Instant::now() >= deadline);
+    assert!(Instant::now() < deadline + Duration::from_millis(500));
+
+    // Being interrupted doesn't restart the wait from scratch.
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        unsafe {
+            libc::signal(
+                libc::SIGALRM, interrupt as *const () as libc::sighandler_t);
+        }
+        let main = unsafe { libc::pthread_self() } as usize;
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(400));
+            unsafe {
+                libc::pthread_kill(main as libc::pthread_t, libc::SIGALRM);
+            }
+        });
+        let deadline = Instant::now() + Duration::from_millis(500);
+        let sig = chan_signal::notify_until(&[Signal::WINCH], deadline);
+        assert_eq!(sig, None);
+        assert!(Instant::now() >= deadline);
+        assert!(Instant::now() < deadline + Duration::from_millis(300));
+    }
+
+    // A deadline that never fires returns the signal, which still reaches
+    // the other subscribers.
+    let r = chan_signal::notify(&[Signal::HUP]);
+    thread::spawn(|| {
+        thread::sleep(Duration::from_millis(100));
+        kill_this(Signal::HUP);
+    });
+    let deadline = Instant::now() + Duration::from_secs(3600);
+    let start = Instant::now();
+    let sig = chan_signal::notify_until(&[Signal::HUP], deadline);
+    assert_eq!(sig, Some(Signal::HUP));
+    assert!(start.elapsed() < Duration::from_secs(60));
+    assert_eq!(r.recv(), Some(Signal::HUP));
+}
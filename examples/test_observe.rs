@@ -0,0 +1,23 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r = chan_signal::notify_observe(&[Signal::WINCH, Signal::TERM])
+        .unwrap();
+    // The default action of WINCH is to do nothing, so we keep going.
+    kill_this(Signal::WINCH);
+    assert_eq!(r.recv(), Some(Signal::WINCH));
+    kill_this(Signal::WINCH);
+    assert_eq!(r.recv(), Some(Signal::WINCH));
+
+    // Signals subscribed with `notify` can't be observed.
+    let _r = chan_signal::notify(&[Signal::USR1]);
+    assert!(chan_signal::notify_observe(&[Signal::USR1]).is_err());
+
+    // The following will terminate the process, as observing TERM doesn't
+    // change its default action.
+    kill_this(Signal::TERM);
+    r.recv();
+    unreachable!();
+}
@@ -0,0 +1,22 @@
+extern crate chan_signal;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let callback_count = count.clone();
+    chan_signal::on_signal(Signal::USR1, move |sig| {
+        assert_eq!(sig, Signal::USR1);
+        callback_count.fetch_add(1, Ordering::SeqCst);
+    });
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,21 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r = chan_signal::notify_once(Signal::USR1);
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+    // Exactly one value is ever sent.
+    assert_eq!(r.recv(), None);
+
+    // The following will terminate the process, since USR1 has been handed
+    // back to its default action. The watcher thread receives it and
+    // re-raises it, so give it a moment.
+    kill_this(Signal::USR1);
+    thread::sleep(Duration::from_secs(1));
+    unreachable!();
+}
@@ -1,5 +1,4 @@
 
-#[macro_use]
 extern crate chan;
 extern crate chan_signal;
 
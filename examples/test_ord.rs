@@ -0,0 +1,45 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
+
+use chan_signal::Signal;
+
+fn main() {
+    let mut set = BTreeSet::new();
+    set.insert(Signal::TERM);
+    set.insert(Signal::HUP);
+    set.insert(Signal::USR2);
+    set.insert(Signal::INT);
+    set.insert(Signal::USR1);
+    let nums: Vec<i32> = set.iter().map(|sig| sig.as_raw()).collect();
+    let mut sorted = nums.clone();
+    sorted.sort();
+    assert_eq!(nums, sorted);
+    assert_eq!(nums.len(), 5);
+
+    assert!(Signal::HUP < Signal::INT);
+    assert_eq!(
+        Signal::USR1 < Signal::TERM,
+        libc::SIGUSR1 < libc::SIGTERM
+    );
+
+    // An `Other` signal with a known number is the named signal.
+    let int = Signal::INT;
+    let other = Signal::Other(libc::SIGINT);
+    assert_eq!(int, other);
+    assert_eq!(int.cmp(&other), Ordering::Equal);
+    assert_eq!(other.name(), "SIGINT");
+    assert_eq!(other.to_string(), int.to_string());
+    let hashed: HashSet<Signal> = vec![int, other].into_iter().collect();
+    assert_eq!(hashed.len(), 1);
+    assert!(!Signal::Other(libc::SIGKILL).is_catchable());
+
+    // Signals that aren't equal never compare as equal.
+    assert_ne!(Signal::Other(1000).cmp(&Signal::RT(0)), Ordering::Equal);
+    assert_ne!(Signal::RT(0).cmp(&Signal::RT(1)), Ordering::Equal);
+    let set: BTreeSet<Signal> =
+        vec![Signal::Other(1000), Signal::Other(1001)].into_iter().collect();
+    assert_eq!(set.len(), 2);
+}
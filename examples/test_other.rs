@@ -0,0 +1,19 @@
+extern crate chan_signal;
+
+use std::convert::TryFrom;
+
+use chan_signal::{DefaultAction, Signal, SignalSet};
+
+fn main() {
+    let other = Signal::Other(100);
+    assert_eq!(other.as_raw(), 100);
+    assert_eq!(other.to_string(), "signal 100");
+    assert_eq!(other.name(), "SIGUNKNOWN");
+    assert_eq!(other.default_action(), DefaultAction::Term);
+    assert!(!Signal::iter().any(|sig| sig == other));
+    // Raw numbers round trip, even if they are unknown.
+    let set = SignalSet::from(&[other, Signal::TERM]);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![Signal::TERM, other]);
+    // Only known signals can be converted from a number.
+    assert!(Signal::try_from(100).is_err());
+}
@@ -0,0 +1,46 @@
+#[macro_use]
+extern crate chan;
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let (s, r) = chan::sync(10);
+    chan_signal::notify_on_many(&s, &[Signal::HUP, Signal::USR1]);
+    // Used to know when the watcher thread is done with each signal.
+    let sync = chan_signal::notify(&[Signal::HUP, Signal::USR1]);
+
+    chan_signal::pause(&s);
+    for &sig in &[Signal::HUP, Signal::USR1, Signal::HUP] {
+        kill_this(sig);
+        assert_eq!(sync.recv(), Some(sig));
+    }
+    // Nothing was sent while paused...
+    chan_select! {
+        default => {},
+        r.recv() -> sig => panic!("received {:?} while paused", sig),
+    }
+
+    // ... and everything arrives in order once resumed.
+    chan_signal::resume(&s);
+    assert_eq!(r.recv(), Some(Signal::HUP));
+    assert_eq!(r.recv(), Some(Signal::USR1));
+    assert_eq!(r.recv(), Some(Signal::HUP));
+
+    // Signals are sent right away again.
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+
+    // Held back signals that don't fit in the channel are dropped.
+    let (s, r) = chan::sync(1);
+    chan_signal::notify_on(&s, Signal::USR2);
+    let sync = chan_signal::notify(&[Signal::USR2]);
+    chan_signal::pause(&s);
+    for _ in 0..3 {
+        kill_this(Signal::USR2);
+        assert_eq!(sync.recv(), Some(Signal::USR2));
+    }
+    chan_signal::resume(&s);
+    assert_eq!(r.recv(), Some(Signal::USR2));
+    assert_eq!(chan_signal::dropped_count(&s), Some(2));
+}
@@ -0,0 +1,11 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    assert!(!chan_signal::pending().unwrap().contains(&Signal::USR1));
+    chan_signal::block(&[Signal::USR1]);
+    kill_this(Signal::USR1);
+    // No watcher thread has been started, so the signal stays pending.
+    assert_eq!(chan_signal::pending().unwrap(), vec![Signal::USR1]);
+}
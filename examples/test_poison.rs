@@ -0,0 +1,25 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::panic;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r1 = chan_signal::notify(&[Signal::USR1]);
+
+    panic::set_hook(Box::new(|_| {}));
+    chan_signal::poison_handlers();
+    let _ = panic::take_hook();
+
+    // Existing subscriptions still receive signals...
+    kill_this(Signal::USR1);
+    assert_eq!(r1.recv(), Some(Signal::USR1));
+
+    // ... and new ones can still be made.
+    let (s2, r2) = chan::sync(1);
+    chan_signal::notify_on(&s2, Signal::USR2);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 1);
+    kill_this(Signal::USR2);
+    assert_eq!(r2.recv(), Some(Signal::USR2));
+}
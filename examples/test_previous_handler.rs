@@ -0,0 +1,57 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::mem;
+use std::ptr;
+
+use chan_signal::{Signal, kill_this};
+
+static mut CALLS: usize = 0;
+
+extern "C" fn handler(_: libc::c_int) {
+    unsafe { CALLS += 1; }
+}
+
+fn current_handler(sig: libc::c_int) -> libc::sighandler_t {
+    unsafe {
+        let mut act: libc::sigaction = mem::zeroed();
+        libc::sigaction(sig, ptr::null(), &mut act);
+        act.sa_sigaction
+    }
+}
+
+fn is_blocked(sig: libc::c_int) -> bool {
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::pthread_sigmask(libc::SIG_BLOCK, ptr::null(), &mut set);
+        libc::sigismember(&set, sig) == 1
+    }
+}
+
+fn main() {
+    unsafe {
+        let mut act: libc::sigaction = mem::zeroed();
+        act.sa_sigaction = handler as *const () as libc::sighandler_t;
+        libc::sigaction(libc::SIGUSR1, &act, ptr::null_mut());
+    }
+
+    let previous = chan_signal::install_with_previous(Signal::USR1).unwrap();
+    assert_eq!(previous.signal(), Signal::USR1);
+    assert!(is_blocked(libc::SIGUSR1));
+    // Someone changes the disposition in the meantime.
+    chan_signal::ignore(&[Signal::USR1]).unwrap();
+    assert_eq!(current_handler(libc::SIGUSR1), libc::SIG_IGN);
+
+    // It can't be restored while something is subscribed, but it can be
+    // once that's gone.
+    let id = chan_signal::notify_fn(&[Signal::USR1], |_| {});
+    assert!(previous.restore().is_err());
+    chan_signal::remove_callback(id);
+
+    previous.restore().unwrap();
+    let expected = handler as *const () as libc::sighandler_t;
+    assert_eq!(current_handler(libc::SIGUSR1), expected);
+    assert!(!is_blocked(libc::SIGUSR1));
+    kill_this(Signal::USR1);
+    assert_eq!(unsafe { CALLS }, 1);
+}
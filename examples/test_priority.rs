@@ -0,0 +1,39 @@
+extern crate chan_signal;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+// Keep the watcher thread busy while `signals` arrive, so that they're all
+// pending by the time it accepts the first one.
+fn burst(busy: &mpsc::Receiver<()>, signals: &[Signal]) {
+    kill_this(Signal::HUP);
+    busy.recv().unwrap();
+    for &sig in signals {
+        kill_this(sig);
+    }
+}
+
+fn main() {
+    let r = chan_signal::notify(&[Signal::USR1, Signal::USR2, Signal::TERM]);
+    let (busy_send, busy) = mpsc::channel();
+    chan_signal::on_signal(Signal::HUP, move |_| {
+        let _ = busy_send.send(());
+        thread::sleep(Duration::from_millis(200));
+    });
+
+    chan_signal::set_priority(Signal::TERM, 10);
+    chan_signal::set_priority(Signal::USR2, 5);
+    burst(&busy, &[Signal::USR1, Signal::USR2, Signal::TERM]);
+    assert_eq!(r.recv(), Some(Signal::TERM));
+    assert_eq!(r.recv(), Some(Signal::USR2));
+    assert_eq!(r.recv(), Some(Signal::USR1));
+
+    // Resetting a priority to `0` puts the signal back in line.
+    chan_signal::set_priority(Signal::TERM, 0);
+    burst(&busy, &[Signal::TERM, Signal::USR2]);
+    assert_eq!(r.recv(), Some(Signal::USR2));
+    assert_eq!(r.recv(), Some(Signal::TERM));
+}
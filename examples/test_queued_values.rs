@@ -0,0 +1,31 @@
+// This example shows how to receive the values sent along with realtime
+// signals.
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+extern crate chan_signal;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn main() {
+    use chan_signal::{Signal, kill_this, queue_to_self};
+
+    let sig = Signal::rt(1).unwrap();
+    let events = chan_signal::notify_events(&[sig, Signal::USR1]);
+    // Realtime signals are queued, so none of these are coalesced.
+    for &value in &[7, 42, 1000] {
+        queue_to_self(sig, value).unwrap();
+    }
+    for &value in &[7, 42, 1000] {
+        let event = events.recv().unwrap();
+        assert_eq!(event.signal(), sig);
+        assert_eq!(event.value(), Some(value));
+    }
+
+    // Signals sent with `kill` have no value.
+    kill_this(Signal::USR1);
+    let event = events.recv().unwrap();
+    assert_eq!(event.signal(), Signal::USR1);
+    assert_eq!(event.value(), None);
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn main() {}
@@ -0,0 +1,37 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::convert::{TryFrom, TryInto};
+
+use chan_signal::{Signal, SignalSet};
+
+fn main() {
+    // Every variant has a distinct number that converts back into it.
+    let rest = [Signal::CHLD, Signal::URG, Signal::WINCH, Signal::IO];
+    let all = SignalSet::fatal()
+        .union(&SignalSet::job_control())
+        .union(&SignalSet::from(&rest));
+    let mut nums = vec![];
+    for sig in all.iter() {
+        assert_eq!(Signal::try_from(sig.as_raw()), Ok(sig));
+        nums.push(sig.as_raw());
+    }
+    nums.dedup();
+    assert_eq!(nums.len(), Signal::ALL.len());
+
+    assert_eq!(Signal::TERM.as_raw(), libc::SIGTERM);
+    assert_eq!(Signal::try_from(libc::SIGUSR1), Ok(Signal::USR1));
+    let err = Signal::try_from(0).unwrap_err();
+    assert_eq!(err.number(), 0);
+    assert!(Signal::try_from(-1).is_err());
+
+    // The standard conversion traits agree with `as_raw`.
+    for sig in all.iter() {
+        let num: i32 = sig.into();
+        assert_eq!(num, sig.as_raw());
+        assert_eq!(i32::from(sig), sig.as_raw());
+        assert_eq!(num.try_into(), Ok(sig));
+    }
+    let res: Result<Signal, _> = 0.try_into();
+    assert!(res.is_err());
+}
@@ -0,0 +1,34 @@
+// This example shows how to subscribe to a realtime signal.
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[macro_use]
+extern crate chan;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+extern crate chan_signal;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+extern crate libc;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn main() {
+    use std::convert::TryFrom;
+
+    use chan_signal::{Signal, kill_this, notify_on};
+
+    let sig = Signal::rt(3).unwrap();
+    assert_eq!(sig, Signal::RT(3));
+    assert!(Signal::rt(200).is_err());
+    assert_eq!(sig.to_string(), "SIGRTMIN+3");
+    assert_eq!(Signal::try_from(libc::SIGRTMIN() + 3).unwrap(), sig);
+
+    let (s, r) = chan::sync(1);
+    notify_on(&s, sig);
+    kill_this(sig);
+    let timeout = chan::after_ms(5000);
+    chan_select! {
+        r.recv() -> got => assert_eq!(got, Some(sig)),
+        timeout.recv() => panic!("realtime signal was not delivered"),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn main() {}
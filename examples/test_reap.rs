@@ -0,0 +1,24 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let keep = chan_signal::notify_std(&[Signal::USR1]);
+    let gone = chan_signal::notify_std(&[Signal::USR1]);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 2);
+
+    // The channel is only reaped once a signal is sent to it.
+    drop(gone);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 2);
+    kill_this(Signal::USR1);
+    assert_eq!(keep.recv().unwrap(), Signal::USR1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 1);
+
+    // Receiving still works for the remaining channel.
+    kill_this(Signal::USR1);
+    assert_eq!(keep.recv().unwrap(), Signal::USR1);
+}
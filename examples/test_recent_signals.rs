@@ -0,0 +1,31 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    assert!(chan_signal::recent_signals(10).is_empty());
+
+    // Nobody receives from this channel, but the history doesn't care.
+    let (s, _r) = chan::sync(0);
+    chan_signal::notify_on_many(&s, &[Signal::USR1, Signal::USR2]);
+    let start = SystemTime::now();
+    for _ in 0..40 {
+        kill_this(Signal::USR1);
+        thread::sleep(Duration::from_millis(5));
+        kill_this(Signal::USR2);
+        thread::sleep(Duration::from_millis(5));
+    }
+    thread::sleep(Duration::from_millis(100));
+
+    let last = chan_signal::recent_signals(3);
+    let sigs: Vec<Signal> = last.iter().map(|&(sig, _)| sig).collect();
+    assert_eq!(sigs, vec![Signal::USR2, Signal::USR1, Signal::USR2]);
+    assert!(last[0].1 <= last[1].1 && last[1].1 <= last[2].1);
+    assert!(start <= last[0].1);
+    // Only the last 64 are remembered.
+    assert_eq!(chan_signal::recent_signals(1000).len(), 64);
+}
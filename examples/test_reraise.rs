@@ -0,0 +1,13 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r = chan_signal::notify(&[Signal::INT]);
+    kill_this(Signal::INT);
+    assert_eq!(r.recv(), Some(Signal::INT));
+
+    // The following will terminate the process with an INT signal, as if
+    // we had never subscribed to it.
+    chan_signal::reraise_default(Signal::INT);
+}
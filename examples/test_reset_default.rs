@@ -0,0 +1,19 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let _r = chan_signal::notify(&[Signal::USR1, Signal::HUP]);
+    // Can't hand back a signal that still has subscribers...
+    assert!(chan_signal::reset_default(&[Signal::HUP]).is_err());
+    // ... unless we force it.
+    chan_signal::force_reset_default(&[Signal::HUP]).unwrap();
+
+    chan_signal::ignore(&[Signal::USR2]).unwrap();
+    chan_signal::reset_default(&[Signal::USR2]).unwrap();
+
+    // The following will terminate the process, since HUP is no longer
+    // blocked and has its default disposition.
+    kill_this(Signal::HUP);
+    unreachable!();
+}
@@ -0,0 +1,31 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::mem;
+use std::ptr;
+
+use chan_signal::Signal;
+
+fn restarts(sig: libc::c_int) -> bool {
+    unsafe {
+        let mut act: libc::sigaction = mem::zeroed();
+        assert_eq!(libc::sigaction(sig, ptr::null(), &mut act), 0);
+        act.sa_flags & libc::SA_RESTART != 0
+    }
+}
+
+fn main() {
+    assert!(!restarts(libc::SIGUSR1));
+    chan_signal::set_restart(Signal::USR1, true).unwrap();
+    assert!(restarts(libc::SIGUSR1));
+    assert!(!restarts(libc::SIGUSR2));
+
+    // The flag survives a later change of disposition.
+    chan_signal::ignore(&[Signal::USR1]).unwrap();
+    assert!(restarts(libc::SIGUSR1));
+
+    chan_signal::set_restart(Signal::USR1, false).unwrap();
+    assert!(!restarts(libc::SIGUSR1));
+
+    assert!(chan_signal::set_restart(Signal::KILL, true).is_err());
+}
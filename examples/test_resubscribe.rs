@@ -0,0 +1,36 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let (s1, r1) = chan::sync(1);
+    chan_signal::notify_on(&s1, Signal::USR1);
+    // Signals must be blocked before threads are spawned.
+    chan_signal::block(&[Signal::USR2]);
+
+    // The receiver subscribes a new channel in response to each signal,
+    // and hands it back.
+    let (snew, rnew) = chan::sync(0);
+    thread::spawn(move || {
+        for sig in r1.iter() {
+            assert_eq!(sig, Signal::USR1);
+            let (s, r) = chan::sync(10);
+            chan_signal::notify_on(&s, Signal::USR2);
+            snew.send(r);
+        }
+    });
+
+    let mut chans = vec![];
+    for _ in 0..10 {
+        kill_this(Signal::USR1);
+        chans.push(rnew.recv().unwrap());
+    }
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 10);
+    kill_this(Signal::USR2);
+    for r in chans {
+        assert_eq!(r.recv(), Some(Signal::USR2));
+    }
+}
@@ -0,0 +1,36 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::mem;
+use std::ptr;
+
+use chan_signal::Signal;
+
+fn is_blocked(sig: libc::c_int) -> bool {
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::pthread_sigmask(libc::SIG_BLOCK, ptr::null(), &mut set);
+        libc::sigismember(&set, sig) == 1
+    }
+}
+
+fn main() {
+    assert!(chan_signal::saved_mask().is_none());
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, libc::SIGUSR2);
+        libc::pthread_sigmask(libc::SIG_BLOCK, &set, ptr::null_mut());
+    }
+
+    let _r = chan_signal::notify(&[Signal::USR1]);
+    assert!(is_blocked(libc::SIGUSR1));
+    let saved = chan_signal::saved_mask().unwrap();
+    assert!(saved.contains(Signal::USR2));
+    assert!(!saved.contains(Signal::USR1));
+    assert_eq!(saved.signals().iter().collect::<Vec<_>>(), vec![Signal::USR2]);
+
+    saved.restore().unwrap();
+    assert!(is_blocked(libc::SIGUSR2));
+    assert!(!is_blocked(libc::SIGUSR1));
+}
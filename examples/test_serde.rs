@@ -0,0 +1,22 @@
+extern crate chan_signal;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+#[cfg(feature = "serde")]
+fn main() {
+    use chan_signal::Signal;
+
+    for sig in Signal::iter() {
+        let json = serde_json::to_string(&sig).unwrap();
+        assert_eq!(json, format!("\"{:?}\"", sig));
+        assert_eq!(serde_json::from_str::<Signal>(&json).unwrap(), sig);
+    }
+    assert_eq!(serde_json::to_string(&Signal::TERM).unwrap(), "\"TERM\"");
+    assert!(serde_json::from_str::<Signal>("\"SIGTERM\"").is_err());
+    let hidden = "\"__NonExhaustiveMatch\"";
+    assert!(serde_json::from_str::<Signal>(hidden).is_err());
+    assert!(serde_json::from_str::<Signal>("15").is_err());
+}
+
+#[cfg(not(feature = "serde"))]
+fn main() {}
@@ -0,0 +1,35 @@
+extern crate chan;
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let (s, r) = chan::sync(1);
+    chan_signal::notify_on(&s, Signal::HUP);
+    let events = chan_signal::notify_events(&[Signal::HUP]);
+    let infos = chan_signal::notify_info(&[Signal::HUP]);
+    let batches = chan_signal::notify_batched(&[Signal::HUP]);
+    kill_this(Signal::HUP);
+    assert_eq!(r.recv(), Some(Signal::HUP));
+    assert_eq!(events.recv().unwrap().signal(), Signal::HUP);
+    assert_eq!(infos.recv().unwrap().signal(), Signal::HUP);
+    assert_eq!(batches.recv(), Some(vec![Signal::HUP]));
+
+    chan_signal::shutdown().unwrap();
+    // Once our sender is gone, the channel is closed since the watcher no
+    // longer holds a copy.
+    drop(s);
+    assert_eq!(r.recv(), None);
+    // The channels that only the watcher had a sender for are closed
+    // right away.
+    assert!(events.recv().is_none());
+    assert!(infos.recv().is_none());
+    assert_eq!(batches.recv(), None);
+    // Shutting down twice is fine.
+    chan_signal::shutdown().unwrap();
+
+    // Subscribing again starts a new watcher.
+    let r = chan_signal::notify(&[Signal::HUP]);
+    kill_this(Signal::HUP);
+    assert_eq!(r.recv(), Some(Signal::HUP));
+}
@@ -0,0 +1,59 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::error::Error;
+use std::io;
+
+use chan_signal::{Signal, SignalError};
+
+fn signal_error(err: &io::Error) -> &SignalError {
+    err.get_ref().and_then(|e| e.downcast_ref::<SignalError>()).unwrap()
+}
+
+fn main() {
+    let einval = || io::Error::from_raw_os_error(libc::EINVAL);
+    let cases = vec![
+        (SignalError::MaskFailed(einval()),
+         "failed to change the signal mask: "),
+        (SignalError::WaitFailed(einval()),
+         "failed to wait for signals: "),
+        (SignalError::Unsupported(Signal::Other(1000)),
+         "signal 1000 is not supported on this platform"),
+        (SignalError::Uncatchable(Signal::KILL),
+         "SIGKILL cannot be caught or blocked"),
+        (SignalError::Fault(Signal::SEGV),
+         "SIGSEGV is raised by faults and cannot be subscribed to"),
+        (SignalError::Excluded(Signal::PROF),
+         "SIGPROF is excluded and cannot be blocked"),
+        (SignalError::ThreadsAlreadySpawned(3),
+         "3 threads were already running when signals were first blocked; \
+          block signals before spawning threads"),
+    ];
+    for (err, want) in cases {
+        let got = err.to_string();
+        assert!(got.starts_with(want), "{:?} != {:?}", got, want);
+        // The OS error is kept as the source.
+        if got.len() > want.len() {
+            assert_eq!(got[want.len()..], einval().to_string());
+            assert!(err.source().is_some());
+        }
+    }
+
+    // The non-panicking functions report them wrapped in an `io::Error`.
+    let err = chan_signal::try_notify(&[Signal::STOP]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    match *signal_error(&err) {
+        SignalError::Uncatchable(Signal::STOP) => {}
+        ref other => panic!("unexpected error: {:?}", other),
+    }
+    let err = chan_signal::try_notify(&[Signal::BUS]).unwrap_err();
+    match *signal_error(&err) {
+        SignalError::Fault(Signal::BUS) => {}
+        ref other => panic!("unexpected error: {:?}", other),
+    }
+    let err = chan_signal::try_block(&[Signal::Other(1000)]).unwrap_err();
+    match *signal_error(&err) {
+        SignalError::Unsupported(Signal::Other(1000)) => {}
+        ref other => panic!("unexpected error: {:?}", other),
+    }
+}
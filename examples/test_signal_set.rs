@@ -0,0 +1,57 @@
+extern crate chan_signal;
+
+use chan_signal::{Signal, SignalSet, kill_this};
+
+fn main() {
+    let mut a = SignalSet::from(&[Signal::INT, Signal::TERM, Signal::USR1]);
+    let b = SignalSet::from(&[Signal::USR1, Signal::USR2]);
+    assert_eq!(a.len(), 3);
+    assert!(a.contains(Signal::INT));
+    assert!(!a.contains(Signal::USR2));
+
+    assert_eq!(a.union(&b), SignalSet::from(&[
+        Signal::INT, Signal::TERM, Signal::USR1, Signal::USR2,
+    ]));
+    assert_eq!(a.intersection(&b), SignalSet::from(Signal::USR1));
+    let diff = SignalSet::from(&[Signal::INT, Signal::TERM]);
+    assert_eq!(a.difference(&b), diff);
+
+    assert!(!a.insert(Signal::INT));
+    assert!(a.remove(Signal::INT));
+    assert!(!a.remove(Signal::INT));
+    assert_eq!(a, SignalSet::from(&[Signal::USR1, Signal::TERM]));
+    assert_eq!(a.iter().collect::<SignalSet>(), a);
+    assert!(SignalSet::new().is_empty());
+
+    let fatal = SignalSet::fatal();
+    assert!(fatal.contains(Signal::TERM) && fatal.contains(Signal::SEGV));
+    assert!(!fatal.contains(Signal::CHLD) && !fatal.contains(Signal::STOP));
+    let job = SignalSet::job_control();
+    assert!(job.contains(Signal::TSTP) && job.contains(Signal::CONT));
+    assert!(job.intersection(&fatal).is_empty());
+
+    let all = SignalSet::all();
+    assert_eq!(all.len(), Signal::ALL.len());
+    assert_eq!(all.union(&fatal), all);
+    assert!(fatal.difference(&all).is_empty());
+    let term = SignalSet::termination();
+    assert_eq!(Vec::from(term.clone()), vec![
+        Signal::HUP, Signal::INT, Signal::QUIT, Signal::TERM,
+    ]);
+    assert_eq!(term.intersection(&fatal), term);
+    assert!(!term.contains(Signal::KILL) && !term.contains(Signal::SEGV));
+
+    // Set algebra identities.
+    assert_eq!(fatal.union(&job).difference(&job), fatal);
+    assert_eq!(
+        fatal.intersection(&term).union(&fatal.difference(&term)),
+        fatal,
+    );
+    assert!(all.difference(&all).is_empty());
+    assert_eq!(SignalSet::from(&Vec::from(all.clone())[..]), all);
+
+    // Sets are accepted wherever signals are subscribed.
+    let r = chan_signal::notify(b);
+    kill_this(Signal::USR2);
+    assert_eq!(r.recv(), Some(Signal::USR2));
+}
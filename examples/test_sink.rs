@@ -0,0 +1,34 @@
+extern crate chan_signal;
+
+use std::sync::{Arc, Mutex};
+
+use chan_signal::{Signal, SignalSink, kill_this};
+
+// A sink that records what it's sent, and gives up after `limit` signals.
+struct Recorder {
+    got: Arc<Mutex<Vec<Signal>>>,
+    limit: usize,
+}
+
+impl SignalSink for Recorder {
+    fn try_send(&self, sig: Signal) -> bool {
+        let mut got = self.got.lock().unwrap();
+        got.push(sig);
+        got.len() < self.limit
+    }
+}
+
+fn main() {
+    let got = Arc::new(Mutex::new(vec![]));
+    let recorder = Recorder { got: got.clone(), limit: 2 };
+    chan_signal::notify_on_sink(recorder, Signal::USR1);
+    // Used to know when the watcher thread is done with each signal.
+    let r = chan_signal::notify(&[Signal::USR1]);
+
+    for _ in 0..3 {
+        kill_this(Signal::USR1);
+        assert_eq!(r.recv(), Some(Signal::USR1));
+    }
+    // The recorder was unsubscribed after the second signal.
+    assert_eq!(*got.lock().unwrap(), vec![Signal::USR1, Signal::USR1]);
+}
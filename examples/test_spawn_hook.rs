@@ -0,0 +1,35 @@
+extern crate chan_signal;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let spawned = Arc::new(AtomicUsize::new(0));
+    let hook_spawned = spawned.clone();
+    chan_signal::set_spawn_hook(move |body| {
+        hook_spawned.fetch_add(1, Ordering::SeqCst);
+        thread::Builder::new()
+            .name("custom-watcher".to_string())
+            .spawn(body)
+            .map(|_| ())
+    }).unwrap();
+    assert_eq!(spawned.load(Ordering::SeqCst), 0);
+
+    let r = chan_signal::notify(&[Signal::USR1]);
+    assert_eq!(spawned.load(Ordering::SeqCst), 1);
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+
+    // It's too late to change the hook now.
+    assert!(chan_signal::set_spawn_hook(|_| Ok(())).is_err());
+
+    // The hook is used again when the watcher is restarted.
+    chan_signal::shutdown().unwrap();
+    let r = chan_signal::notify(&[Signal::USR2]);
+    assert_eq!(spawned.load(Ordering::SeqCst), 2);
+    kill_this(Signal::USR2);
+    assert_eq!(r.recv(), Some(Signal::USR2));
+}
@@ -0,0 +1,40 @@
+extern crate chan_signal;
+
+use chan_signal::Signal;
+
+fn main() {
+    // These ids are part of the public API and must never change.
+    let pinned = [
+        (Signal::HUP, 0), (Signal::INT, 1), (Signal::QUIT, 2),
+        (Signal::ILL, 3), (Signal::ABRT, 4), (Signal::FPE, 5),
+        (Signal::KILL, 6), (Signal::SEGV, 7), (Signal::PIPE, 8),
+        (Signal::ALRM, 9), (Signal::TERM, 10), (Signal::USR1, 11),
+        (Signal::USR2, 12), (Signal::CHLD, 13), (Signal::CONT, 14),
+        (Signal::STOP, 15), (Signal::TSTP, 16), (Signal::TTIN, 17),
+        (Signal::TTOU, 18), (Signal::BUS, 19), (Signal::PROF, 20),
+        (Signal::SYS, 21), (Signal::TRAP, 22), (Signal::URG, 23),
+        (Signal::VTALRM, 24), (Signal::XCPU, 25), (Signal::XFSZ, 26),
+        (Signal::IO, 27), (Signal::WINCH, 28),
+        (Signal::RT(0), 128), (Signal::RT(5), 133), (Signal::RT(126), 254),
+    ];
+    for &(sig, id) in pinned.iter() {
+        assert_eq!(sig.stable_id(), id, "{:?}", sig);
+        assert_eq!(Signal::from_stable_id(id), Some(sig));
+    }
+
+    assert_eq!(Signal::Other(100).stable_id(), 255);
+    assert_eq!(Signal::RT(127).stable_id(), 255);
+    assert_eq!(Signal::from_stable_id(255), None);
+    assert_eq!(Signal::from_stable_id(31), None);
+    assert_eq!(Signal::from_stable_id(127), None);
+    for id in 0..=255u8 {
+        if let Some(sig) = Signal::from_stable_id(id) {
+            assert_eq!(sig.stable_id(), id);
+        }
+    }
+
+    // Signals can be used as keys in hash maps.
+    let mut seen = ::std::collections::HashSet::new();
+    assert!(seen.insert(Signal::INT));
+    assert!(!seen.insert(Signal::INT));
+}
@@ -0,0 +1,30 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    assert_eq!(chan_signal::stats().get(Signal::USR1).received(), 0);
+
+    let r1 = chan_signal::notify(&[Signal::USR1, Signal::USR2]);
+    let r2 = chan_signal::notify(&[Signal::USR1]);
+    for _ in 0..3 {
+        kill_this(Signal::USR1);
+        thread::sleep(Duration::from_millis(50));
+    }
+    kill_this(Signal::USR2);
+    thread::sleep(Duration::from_millis(50));
+
+    let stats = chan_signal::stats();
+    // Every `USR1` was sent on both channels.
+    assert_eq!(stats.get(Signal::USR1).received(), 3);
+    assert_eq!(stats.get(Signal::USR1).delivered(), 6);
+    assert_eq!(stats.get(Signal::USR2).received(), 1);
+    assert_eq!(stats.get(Signal::USR2).delivered(), 1);
+    assert_eq!(stats.get(Signal::HUP).received(), 0);
+    let seen: Vec<Signal> = stats.iter().map(|(sig, _)| sig).collect();
+    assert_eq!(seen, vec![Signal::USR1, Signal::USR2]);
+    drop((r1, r2));
+}
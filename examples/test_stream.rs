@@ -0,0 +1,17 @@
+extern crate chan_signal;
+#[cfg(feature = "async")]
+extern crate futures;
+
+#[cfg(feature = "async")]
+fn main() {
+    use futures::Stream;
+
+    use chan_signal::{Signal, kill_this};
+
+    let mut signals = chan_signal::notify_stream(&[Signal::USR1]).wait();
+    kill_this(Signal::USR1);
+    assert_eq!(signals.next(), Some(Ok(Signal::USR1)));
+}
+
+#[cfg(not(feature = "async"))]
+fn main() {}
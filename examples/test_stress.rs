@@ -0,0 +1,24 @@
+extern crate chan_signal;
+
+use std::thread;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r1 = chan_signal::notify(&[Signal::USR1]);
+    let r2 = chan_signal::notify(&[Signal::USR2]);
+    // Keep the watcher thread busy with a second signal in the background.
+    let noise = thread::spawn(move || {
+        for _ in 0..1000 {
+            kill_this(Signal::USR2);
+        }
+    });
+    // Every round trip must succeed, or the watcher thread has died.
+    for _ in 0..1000 {
+        kill_this(Signal::USR1);
+        assert_eq!(r1.recv(), Some(Signal::USR1));
+    }
+    noise.join().unwrap();
+    kill_this(Signal::USR2);
+    assert_eq!(r2.recv(), Some(Signal::USR2));
+}
@@ -0,0 +1,27 @@
+extern crate chan;
+extern crate chan_signal;
+
+use chan_signal::Signal;
+
+fn main() {
+    assert_eq!(chan_signal::subscriber_count(Signal::HUP), 0);
+
+    let (s, r) = chan::sync(1);
+    chan_signal::notify_on(&s, Signal::HUP);
+    // Subscribing the same channel again doesn't add a subscriber.
+    chan_signal::notify_on(&s, Signal::HUP);
+    assert_eq!(chan_signal::subscriber_count(Signal::HUP), 1);
+
+    let _r2 = chan_signal::notify(&[Signal::HUP, Signal::USR2]);
+    chan_signal::on_signal(Signal::HUP, |_| {});
+    assert_eq!(chan_signal::subscriber_count(Signal::HUP), 3);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 1);
+
+    // Dropping the receiver doesn't unsubscribe the channel.
+    drop(r);
+    assert_eq!(chan_signal::subscriber_count(Signal::HUP), 3);
+
+    chan_signal::force_reset_default(&[Signal::HUP]).unwrap();
+    assert_eq!(chan_signal::subscriber_count(Signal::HUP), 0);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 1);
+}
@@ -0,0 +1,23 @@
+extern crate chan;
+extern crate chan_signal;
+
+use chan_signal::Signal;
+
+fn main() {
+    let (s, _r) = chan::sync(1);
+    assert_eq!(chan_signal::subscriptions(&s), vec![]);
+
+    chan_signal::notify_on(&s, Signal::TERM);
+    chan_signal::notify_on_many(&s, &[Signal::USR1, Signal::HUP]);
+    let mut subs = chan_signal::subscriptions(&s);
+    subs.sort_by_key(|sig| sig.as_raw());
+    let mut want = vec![Signal::HUP, Signal::USR1, Signal::TERM];
+    want.sort_by_key(|sig| sig.as_raw());
+    assert_eq!(subs, want);
+
+    // Other channels aren't affected.
+    let (other, _r) = chan::sync(1);
+    chan_signal::notify_on(&other, Signal::USR2);
+    assert_eq!(chan_signal::subscriptions(&other), vec![Signal::USR2]);
+    assert_eq!(chan_signal::subscriptions(&s).len(), 3);
+}
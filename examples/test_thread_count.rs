@@ -0,0 +1,22 @@
+extern crate chan_signal;
+
+use std::sync::mpsc;
+use std::thread;
+
+use chan_signal::Signal;
+
+fn main() {
+    assert_eq!(chan_signal::thread_count_at_init(), None);
+
+    // Start a thread before any signals are blocked, which is a mistake.
+    let (done, wait) = mpsc::channel::<()>();
+    let early = thread::spawn(move || { let _ = wait.recv(); });
+    let _r = chan_signal::notify(&[Signal::USR1]);
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        assert_eq!(chan_signal::thread_count_at_init(), Some(2));
+    } else {
+        assert_eq!(chan_signal::thread_count_at_init(), None);
+    }
+    drop(done);
+    early.join().unwrap();
+}
@@ -0,0 +1,32 @@
+extern crate chan_signal;
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::Signal;
+
+#[cfg(target_os = "linux")]
+fn thread_names() -> Vec<String> {
+    let mut names = vec![];
+    for entry in fs::read_dir("/proc/self/task").unwrap() {
+        let path = entry.unwrap().path().join("comm");
+        let mut name = String::new();
+        File::open(path).unwrap().read_to_string(&mut name).unwrap();
+        names.push(name.trim().to_string());
+    }
+    names
+}
+
+#[cfg(target_os = "linux")]
+fn main() {
+    chan_signal::set_watcher_thread_name("sigwatch");
+    let _r = chan_signal::notify(&[Signal::USR1]);
+    // The new thread sets its own name, so give it a moment to start.
+    thread::sleep(Duration::from_millis(100));
+    assert!(thread_names().iter().any(|name| name == "sigwatch"));
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {}
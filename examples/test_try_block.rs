@@ -0,0 +1,26 @@
+extern crate chan_signal;
+extern crate libc;
+
+use std::mem;
+use std::ptr;
+
+use chan_signal::Signal;
+
+fn is_blocked(sig: libc::c_int) -> bool {
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::pthread_sigmask(libc::SIG_BLOCK, ptr::null(), &mut set);
+        libc::sigismember(&set, sig) == 1
+    }
+}
+
+fn main() {
+    assert!(!is_blocked(libc::SIGUSR1));
+    chan_signal::try_block(&[Signal::USR1]).unwrap();
+    assert!(is_blocked(libc::SIGUSR1));
+    assert!(!is_blocked(libc::SIGUSR2));
+
+    chan_signal::try_block_all_subscribable().unwrap();
+    assert!(is_blocked(libc::SIGUSR2));
+    assert!(is_blocked(libc::SIGHUP));
+}
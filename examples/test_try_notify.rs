@@ -0,0 +1,27 @@
+extern crate chan;
+extern crate chan_signal;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    // Signal numbers that the OS doesn't accept are reported as errors.
+    let bogus = Signal::Other(1000);
+    let (s, _r) = chan::sync(1);
+    assert!(chan_signal::try_notify_on(&s, bogus).is_err());
+    assert!(chan_signal::try_notify(&[Signal::USR1, bogus]).is_err());
+    assert!(chan_signal::try_block(&[bogus]).is_err());
+
+    // Nothing was subscribed by the failed calls.
+    assert_eq!(chan_signal::subscriber_count(bogus), 0);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 0);
+    assert_eq!(chan_signal::dropped_count(&s), None);
+
+    // A failure doesn't undo an earlier subscription of the same channel.
+    chan_signal::try_notify_on(&s, Signal::USR2).unwrap();
+    assert!(chan_signal::try_notify_on_many(&s, &[bogus]).is_err());
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 1);
+
+    let r = chan_signal::try_notify(&[Signal::USR1]).unwrap();
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+}
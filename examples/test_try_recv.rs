@@ -0,0 +1,23 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let r = chan_signal::notify(&[Signal::USR1]);
+    assert_eq!(chan_signal::try_recv(&r), None);
+
+    kill_this(Signal::USR1);
+    let mut received = None;
+    for _ in 0..100 {
+        received = chan_signal::try_recv(&r);
+        if received.is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(received, Some(Signal::USR1));
+    assert_eq!(chan_signal::try_recv(&r), None);
+}
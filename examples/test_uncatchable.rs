@@ -0,0 +1,63 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::panic;
+use std::time::{Duration, Instant};
+
+use chan_signal::{Signal, SignalError, kill_this};
+
+fn main() {
+    for &sig in &[Signal::KILL, Signal::STOP] {
+        let err = chan_signal::try_notify(&[sig, Signal::TERM]).unwrap_err();
+        assert!(err.to_string().contains(&sig.to_string()), "{}", err);
+        let (s, _r) = chan::sync(1);
+        assert!(chan_signal::try_notify_on(&s, sig).is_err());
+        assert_eq!(chan_signal::subscriber_count(sig), 0);
+    }
+    // Nothing from the failed calls was subscribed.
+    assert_eq!(chan_signal::subscriber_count(Signal::TERM), 0);
+
+    // When subscribing an existing channel, the signals that can be
+    // subscribed to still are, and the first rejected one is reported.
+    let (s, many) = chan::sync(1);
+    let err = chan_signal::try_notify_on_many(
+        &s, &[Signal::KILL, Signal::TERM, Signal::STOP],
+    ).unwrap_err();
+    match err.get_ref().and_then(|e| e.downcast_ref::<SignalError>()) {
+        Some(&SignalError::Uncatchable(Signal::KILL)) => {}
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(chan_signal::subscriptions(&s), vec![Signal::TERM]);
+    assert_eq!(chan_signal::subscriber_count(Signal::TERM), 1);
+    kill_this(Signal::TERM);
+    assert_eq!(many.recv(), Some(Signal::TERM));
+
+    // The infallible API panics instead.
+    panic::set_hook(Box::new(|_| {}));
+    assert!(panic::catch_unwind(|| chan_signal::notify(&[Signal::KILL]))
+            .is_err());
+    // Rather than waiting for a signal that can never arrive.
+    assert!(panic::catch_unwind(|| chan_signal::wait_for(&[Signal::KILL]))
+            .is_err());
+    let deadline = Instant::now() + Duration::from_secs(60);
+    assert!(panic::catch_unwind(|| {
+        chan_signal::notify_until(&[Signal::STOP], deadline)
+    }).is_err());
+    // Fault signals are refused by every kind of subscription, and they
+    // aren't blocked.
+    assert!(panic::catch_unwind(|| {
+        chan_signal::notify_events(&[Signal::SEGV])
+    }).is_err());
+    assert!(panic::catch_unwind(|| {
+        chan_signal::notify_info(&[Signal::BUS])
+    }).is_err());
+    let _ = panic::take_hook();
+    assert!(!chan_signal::is_blocked(Signal::SEGV).unwrap());
+    assert!(!chan_signal::is_blocked(Signal::BUS).unwrap());
+    assert_eq!(chan_signal::subscriber_count(Signal::KILL), 0);
+
+    // Subscribing to TERM on its own still works.
+    let r = chan_signal::try_notify(&[Signal::TERM]).unwrap();
+    kill_this(Signal::TERM);
+    assert_eq!(r.recv(), Some(Signal::TERM));
+}
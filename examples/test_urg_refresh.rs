@@ -0,0 +1,40 @@
+// A `URG` sent by another process isn't mistaken for the one that wakes up
+// the watcher when it needs to start waiting on a new realtime signal.
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[macro_use]
+extern crate chan;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+extern crate chan_signal;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn main() {
+    use std::process::{self, Command};
+
+    use chan_signal::Signal;
+
+    let r = chan_signal::notify(&[Signal::URG]);
+    for _ in 0..3 {
+        chan_signal::request_refresh();
+        let status = Command::new("kill")
+            .arg("-URG").arg(process::id().to_string())
+            .status().unwrap();
+        assert!(status.success());
+        let timeout = chan::after_ms(5000);
+        chan_select! {
+            r.recv() -> got => assert_eq!(got, Some(Signal::URG)),
+            timeout.recv() => panic!("URG was swallowed by a refresh"),
+        }
+    }
+
+    // The wakeup itself is still swallowed.
+    chan_signal::block(&[Signal::RT(1)]);
+    let timeout = chan::after_ms(200);
+    chan_select! {
+        r.recv() -> got => panic!("received the watcher's wakeup: {:?}", got),
+        timeout.recv() => {},
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn main() {}
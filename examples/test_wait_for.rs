@@ -0,0 +1,21 @@
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    // The signals must be blocked before another thread is spawned, so
+    // subscribe something else to them first.
+    let _keep = chan_signal::notify(&[Signal::USR1, Signal::USR2]);
+    thread::spawn(|| {
+        thread::sleep(Duration::from_millis(100));
+        kill_this(Signal::USR2);
+    });
+    let got = chan_signal::wait_for(&[Signal::USR1, Signal::USR2]);
+    assert_eq!(got, Signal::USR2);
+    // The subscription made by `wait_for` is gone.
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 1);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR2), 1);
+}
@@ -0,0 +1,33 @@
+#[macro_use]
+extern crate chan;
+extern crate chan_signal;
+
+use std::thread;
+use std::time::Duration;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    assert!(!chan_signal::watcher_alive());
+    let r = chan_signal::notify(&[Signal::HUP]);
+    assert!(chan_signal::watcher_alive());
+
+    // The signal that makes the watcher panic is lost, but a new watcher
+    // takes over.
+    chan_signal::panic_watcher();
+    kill_this(Signal::HUP);
+    thread::sleep(Duration::from_millis(100));
+    assert!(chan_signal::watcher_alive());
+
+    kill_this(Signal::HUP);
+    let timeout = chan::after(Duration::from_secs(5));
+    chan_select! {
+        r.recv() -> sig => assert_eq!(sig, Some(Signal::HUP)),
+        timeout.recv() => panic!("HUP not delivered after restart"),
+    }
+
+    assert!(chan_signal::watcher_error().is_none());
+
+    chan_signal::shutdown().unwrap();
+    assert!(!chan_signal::watcher_alive());
+}
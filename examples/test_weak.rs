@@ -0,0 +1,31 @@
+extern crate chan;
+extern crate chan_signal;
+
+use std::sync::Arc;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let (s, r) = chan::sync(1);
+    let s = Arc::new(s);
+    chan_signal::notify_on_weak(&s, Signal::USR1);
+    let done = chan_signal::notify(&[Signal::USR1, Signal::USR2]);
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 2);
+
+    kill_this(Signal::USR1);
+    assert_eq!(r.recv(), Some(Signal::USR1));
+    assert_eq!(done.recv(), Some(Signal::USR1));
+
+    // This crate doesn't keep the sender alive, so the channel is closed
+    // as soon as ours is dropped.
+    drop(s);
+    assert_eq!(r.recv(), None);
+
+    // The subscription is removed the next time its signal arrives. Once
+    // the `USR2` after it is received, the `USR1` was fully delivered.
+    kill_this(Signal::USR1);
+    assert_eq!(done.recv(), Some(Signal::USR1));
+    kill_this(Signal::USR2);
+    assert_eq!(done.recv(), Some(Signal::USR2));
+    assert_eq!(chan_signal::subscriber_count(Signal::USR1), 1);
+}
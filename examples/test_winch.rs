@@ -0,0 +1,25 @@
+#[macro_use]
+extern crate chan;
+extern crate chan_signal;
+extern crate libc;
+
+use chan_signal::{Signal, kill_this};
+
+fn main() {
+    let sizes = chan_signal::notify_winch();
+    kill_this(Signal::WINCH);
+    let timeout = chan::after_ms(500);
+    let tty = unsafe {
+        libc::isatty(libc::STDIN_FILENO) == 1
+        || libc::isatty(libc::STDOUT_FILENO) == 1
+    };
+    chan_select! {
+        sizes.recv() -> size => {
+            assert!(tty, "got a size without a terminal");
+            assert!(size.is_some());
+        },
+        timeout.recv() => {
+            assert!(!tty, "no size was sent for a terminal");
+        },
+    }
+}
@@ -0,0 +1,12 @@
+// This example shows how to block until one of several signals arrives,
+// which is all that many command line tools need.
+
+extern crate chan_signal;
+
+use chan_signal::Signal;
+
+fn main() {
+    println!("Send an INT or TERM signal my way!");
+    let signal = chan_signal::wait_for(&[Signal::INT, Signal::TERM]);
+    println!("Received {}, bye!", signal);
+}
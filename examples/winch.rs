@@ -0,0 +1,27 @@
+// This example shows how to follow the size of the terminal. Resize the
+// terminal window to see it change, and press ^C to quit.
+
+#[macro_use]
+extern crate chan;
+extern crate chan_signal;
+
+use chan_signal::Signal;
+
+fn main() {
+    let int = chan_signal::notify(&[Signal::INT]);
+    let sizes = chan_signal::notify_winch();
+    println!("Resize this terminal, or press ^C to quit.");
+    loop {
+        chan_select! {
+            int.recv() => {
+                println!("Bye!");
+                return;
+            },
+            sizes.recv() -> size => {
+                let (rows, cols) = size.unwrap();
+                println!("The terminal is now {} rows by {} columns.",
+                         rows, cols);
+            },
+        }
+    }
+}
@@ -60,12 +60,24 @@ You can see this example in action by running `cargo run --example select`
 in the root directory of this crate's
 [repository](https://github.com/BurntSushi/chan-signal).
 
-# Platform support (no Windows support)
+# Platform support
 
 This should work on Unix platforms supported by Rust itself.
 
-There is no Windows support at all. I welcome others to either help me add it
-or help educate me so that I may one day add it.
+On Fuchsia, only `HUP`, `INT`, `TERM`, `ALRM`, `USR1`, `USR2` and `CHLD`
+can be subscribed to. Its POSIX layer doesn't deliver the others.
+
+Other targets, e.g., Windows or `wasm32-wasip1`, don't have POSIX signals.
+On those, the crate still builds with the same API, but it is only a stub:
+no signal is ever received. The channels it returns never receive
+anything, closures are never called, functions that would change how
+signals are handled return an `io::Error` (usually wrapping
+`SignalError::Unsupported`) and functions that report on signals report
+that none arrived. This lets programs that build for several targets keep
+a single code path. Only the signals that exist everywhere are available,
+e.g., not `PWR`, and neither are functions that only exist on some Unix
+platforms, like `signal_fd`. Windows console events like Ctrl-C aren't
+supported.
 
 
 # How it works
@@ -96,423 +108,88 @@ following:
 * No other threads may call `sigwait`. When a signal is delivered, only one
   `sigwait` is indeterminately unblocked.
 
+With the `kqueue` feature on macOS and the BSDs, the watcher thread instead
+waits on a kqueue with an `EVFILT_SIGNAL` filter for each signal, and then
+accepts the signal with `sigwait`. Unlike `sigwait`, kqueue counts how many
+times a signal was sent while it was pending, so a burst of the same signal
+is delivered that many times instead of once. Everything else, including
+the restrictions above, is the same. On other platforms, the feature does
+nothing.
+
+
+# Debugging
+
+If signals don't seem to arrive, enable the `log` feature. The watcher
+thread then logs when it starts, which signals get blocked, every signal
+returned by `sigwait` and every delivery to (or drop for) a subscriber.
+Running with `RUST_LOG=chan_signal=trace` (or the equivalent for your
+logger) shows the whole lifecycle of each signal.
+
 
 # Future work
 
 This crate exposes the simplest API I could think of. As a result, a few
 additions may be warranted:
 
-* Expand the set of signals. (Requires figuring out platform differences.)
-* Allow channel unsubscription.
-* Allow callers to reset the signal mask? (Seems hard.)
-* Support Windows.
+* Unsubscribe a single channel from some of its signals. Today, a channel is
+  only unsubscribed when its signals are handed back with
+  `force_reset_default`, when it was subscribed with `notify_on_weak` and
+  its sender is dropped, or by `shutdown`. (Closures registered with
+  `notify_fn` can be removed with `remove_callback`.)
+* Restore the signal mask of threads other than the calling one. (Seems
+  hard.)
+* Receive console events like Ctrl-C on Windows, where only a stub that
+  never receives anything is provided.
 */
 #![deny(missing_docs)]
+// Only the Unix backend logs anything.
+#![cfg_attr(not(unix), allow(unused_macros))]
 
 extern crate bit_set;
 #[macro_use] extern crate chan;
+#[cfg(feature = "crossbeam-channel")] extern crate crossbeam_channel;
+#[cfg(feature = "async")] extern crate futures;
 #[macro_use] extern crate lazy_static;
 extern crate libc;
-
-use std::collections::HashMap;
-use std::io;
-use std::mem;
-use std::ptr;
-use std::sync::Mutex;
-use std::thread;
-
-use bit_set::BitSet;
-use chan::Sender;
-use libc::{
-    // POSIX.1-2008, minus SIGPOLL (not in some BSD, use SIGIO)
-    SIGHUP, SIGINT, SIGQUIT, SIGILL, SIGABRT, SIGFPE, SIGKILL,
-    SIGSEGV, SIGPIPE, SIGALRM, SIGTERM, SIGUSR1, SIGUSR2,
-    SIGCHLD, SIGCONT, SIGSTOP, SIGTSTP, SIGTTIN, SIGTTOU,
-    SIGBUS, SIGPROF, SIGSYS, SIGTRAP, SIGURG, SIGVTALRM,
-    SIGXCPU, SIGXFSZ,
-
-    // Common Extensions (SIGINFO and SIGEMT not in libc)
-    SIGIO,
-    SIGWINCH,
-
-    SIG_BLOCK,
-    SIG_SETMASK,
+#[cfg(feature = "log")] extern crate log;
+#[cfg(feature = "serde")] extern crate serde;
+
+// Logging of what the watcher thread does, enabled by the `log` feature.
+// Without it, the arguments are only type checked, and nothing ends up in
+// the compiled code.
+#[cfg(feature = "log")]
+macro_rules! log_trace { ($($arg:tt)*) => { ::log::trace!($($arg)*) } }
+#[cfg(feature = "log")]
+macro_rules! log_debug { ($($arg:tt)*) => { ::log::debug!($($arg)*) } }
+#[cfg(feature = "log")]
+macro_rules! log_warn { ($($arg:tt)*) => { ::log::warn!($($arg)*) } }
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_trace {
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } }
+}
+#[cfg(not(feature = "log"))]
+macro_rules! log_debug {
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } }
+}
+#[cfg(not(feature = "log"))]
+macro_rules! log_warn {
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } }
+}
+
+pub use signal::{
+    DefaultAction, Signal, SignalError, SignalSet, SignalSetIter,
+    UnknownSignal,
 };
-use libc::kill;
-use libc::getpid;
-
-lazy_static! {
-    static ref HANDLERS: Mutex<HashMap<Sender<Signal>, BitSet>> = {
-        init();
-        Mutex::new(HashMap::new())
-    };
-}
-
-/// Create a new channel subscribed to the given signals.
-///
-/// The channel returned is never closed.
-///
-/// This is a convenience function for subscribing to multiple signals at once.
-/// See the documentation of `notify_on` for details.
-///
-/// The channel returned has a small buffer to prevent signals from being
-/// dropped.
-///
-/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
-/// PROCESS.**
-///
-/// # Example
-///
-/// ```no_run
-/// use chan_signal::Signal;
-///
-/// let signal = chan_signal::notify(&[Signal::INT, Signal::TERM]);
-///
-/// // Blocks until this process is sent an INT or TERM signal.
-/// // Since the channel is never closed, we can unwrap the received value.
-/// signal.recv().unwrap();
-/// ```
-pub fn notify(signals: &[Signal]) -> chan::Receiver<Signal> {
-    let (s, r) = chan::sync(100);
-    for &sig in signals {
-        notify_on(&s, sig);
-    }
-    // dropping `s` is OK because `notify_on` acquires one.
-    r
-}
-
-/// Subscribe to a signal on a channel.
-///
-/// When `signal` is delivered to this process, it will be sent on the channel
-/// given.
-///
-/// Note that a signal is sent using a non-blocking send. Namely, if the
-/// channel's buffer is full (or it has no buffer) and it isn't ready to
-/// rendezvous, then the signal will be dropped.
-///
-/// There is currently no way to unsubscribe. Moreover, the channel given
-/// here will be alive for the lifetime of the process. Therefore, the channel
-/// will never be closed.
-///
-/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
-/// PROCESS.**
-pub fn notify_on(chan: &Sender<Signal>, signal: Signal) {
-    let mut subs = HANDLERS.lock().unwrap();
-    if subs.contains_key(chan) {
-        subs.get_mut(chan).unwrap().insert(signal.as_sig() as usize);
-    } else {
-        let mut sigs = BitSet::new();
-        sigs.insert(signal.as_sig() as usize);
-        subs.insert((*chan).clone(), sigs);
-    }
-
-    // Make sure that the signal that we want notifications on is blocked
-    // It does not matter if we block the same signal twice.
-    block(&[signal]);
-}
-
-/// Block all given signals without receiving notifications.
-///
-/// If a signal has also been passed to `notify`/`notify_on` this function
-/// does not have any effect in terms of that signal.
-///
-/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
-/// PROCESS.**
-pub fn block(signals: &[Signal]) {
-    let mut block = SigSet::empty();
-    for signal in signals {
-        block.add(signal.as_sig()).unwrap();
-    }
-    block.thread_block_signals().unwrap();
-}
-
-/// Block all subscribable signals.
-///
-/// Calling this function effectively restores the default behavior of
-/// version <= 0.2.0 of this library.
-///
-/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
-/// PROCESS.**
-pub fn block_all_subscribable() {
-    SigSet::subscribable().thread_block_signals().unwrap();
-}
-
-fn init() {
-    // First:
-    // Get the curren thread_mask. (We cannot just overwrite the threadmask with
-    // an empty one because this function is executed lazily.
-    let saved_mask = SigSet::current().unwrap();
-
-    // Then:
-    // Block all signals in this thread. The signal mask will then be inherited
-    // by the worker thread.
-    SigSet::subscribable().thread_set_signal_mask().unwrap();
-    thread::spawn(move || {
-        let mut listen = SigSet::subscribable();
-
-        loop {
-            let sig = listen.wait().unwrap();
-            let subs = HANDLERS.lock().unwrap();
-            for (s, sigs) in subs.iter() {
-                if !sigs.contains(sig as usize) {
-                    continue;
-                }
-                chan_select! {
-                    default => {},
-                    s.send(Signal::new(sig)) => {},
-                }
-            }
-        }
-    });
-
-    // Now:
-    // Reset to the previously saved sigmask.
-    // This whole procedure is necessary, as we cannot rely on the worker thread
-    // starting fast enough to set its signal mask. Otherwise an early SIGTERM or
-    // similar may take down the process even though the main thread has blocked
-    // the signal.
-    saved_mask.thread_set_signal_mask().unwrap();
-}
-
-/// Kill the current process. (Only used in tests.)
-#[doc(hidden)]
-pub fn kill_this(sig: Signal) {
-    unsafe { kill(getpid(), sig.as_sig()); }
-}
-
-type Sig = libc::c_int;
-
-/// The set of subscribable signals.
-///
-/// After the first call to `notify_on` (or `notify`), precisely this set of
-/// signals are set to blocked status.
-#[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum Signal {
-    HUP,
-    INT,
-    QUIT,
-    ILL,
-    ABRT,
-    FPE,
-    KILL,
-    SEGV,
-    PIPE,
-    ALRM,
-    TERM,
-    USR1,
-    USR2,
-    CHLD,
-    CONT,
-    STOP,
-    TSTP,
-    TTIN,
-    TTOU,
-    BUS,
-    PROF,
-    SYS,
-    TRAP,
-    URG,
-    VTALRM,
-    XCPU,
-    XFSZ,
-    IO,
-    WINCH,
-    #[doc(hidden)]
-    __NonExhaustiveMatch,
-}
-
-impl Signal {
-    fn new(sig: Sig) -> Signal {
-        match sig {
-            SIGHUP => Signal::HUP,
-            SIGINT => Signal::INT,
-            SIGQUIT => Signal::QUIT,
-            SIGILL => Signal::ILL,
-            SIGABRT => Signal::ABRT,
-            SIGFPE => Signal::FPE,
-            SIGKILL => Signal::KILL,
-            SIGSEGV => Signal::SEGV,
-            SIGPIPE => Signal::PIPE,
-            SIGALRM => Signal::ALRM,
-            SIGTERM => Signal::TERM,
-            SIGUSR1 => Signal::USR1,
-            SIGUSR2 => Signal::USR2,
-            SIGCHLD => Signal::CHLD,
-            SIGCONT => Signal::CONT,
-            SIGSTOP => Signal::STOP,
-            SIGTSTP => Signal::TSTP,
-            SIGTTIN => Signal::TTIN,
-            SIGTTOU => Signal::TTOU,
-            SIGBUS => Signal::BUS,
-            SIGPROF => Signal::PROF,
-            SIGSYS => Signal::SYS,
-            SIGTRAP => Signal::TRAP,
-            SIGURG => Signal::URG,
-            SIGVTALRM => Signal::VTALRM,
-            SIGXCPU => Signal::XCPU,
-            SIGXFSZ => Signal::XFSZ,
-            SIGIO => Signal::IO,
-            SIGWINCH => Signal::WINCH,
-            sig => panic!("unsupported signal number: {}", sig),
-        }
-    }
-
-    fn as_sig(self) -> Sig {
-        match self {
-            Signal::HUP => SIGHUP,
-            Signal::INT => SIGINT,
-            Signal::QUIT => SIGQUIT,
-            Signal::ILL => SIGILL,
-            Signal::ABRT => SIGABRT,
-            Signal::FPE => SIGFPE,
-            Signal::KILL => SIGKILL,
-            Signal::SEGV => SIGSEGV,
-            Signal::PIPE => SIGPIPE,
-            Signal::ALRM => SIGALRM,
-            Signal::TERM => SIGTERM,
-            Signal::USR1 => SIGUSR1,
-            Signal::USR2 => SIGUSR2,
-            Signal::CHLD => SIGCHLD,
-            Signal::CONT => SIGCONT,
-            Signal::STOP => SIGSTOP,
-            Signal::TSTP => SIGTSTP,
-            Signal::TTIN => SIGTTIN,
-            Signal::TTOU => SIGTTOU,
-            Signal::BUS => SIGBUS,
-            Signal::PROF => SIGPROF,
-            Signal::SYS => SIGSYS,
-            Signal::TRAP => SIGTRAP,
-            Signal::URG => SIGURG,
-            Signal::VTALRM => SIGVTALRM,
-            Signal::XCPU => SIGXCPU,
-            Signal::XFSZ => SIGXFSZ,
-            Signal::IO => SIGIO,
-            Signal::WINCH => SIGWINCH,
-            Signal::__NonExhaustiveMatch => unreachable!(),
-        }
-    }
-}
-
-/// Safe wrapper around `sigset_t`.
-struct SigSet(sigset_t);
-
-impl SigSet {
-    fn empty() -> SigSet {
-        let mut set = unsafe { mem::zeroed() };
-        unsafe { sigemptyset(&mut set) };
-        SigSet(set)
-    }
-
-    fn current() -> io::Result<SigSet> {
-        let mut set = unsafe { mem::zeroed() };
-        let ecode = unsafe {
-            pthread_sigmask(SIG_SETMASK, ptr::null_mut(), &mut set)
-        };
-        ok_errno(SigSet(set), ecode)
-    }
-
-    /// Creates a new signal set with precisely the signals we're limited
-    /// to subscribing to.
-    fn subscribable() -> SigSet {
-        let mut set = SigSet::empty();
-        set.add(SIGHUP).unwrap();
-        set.add(SIGINT).unwrap();
-        set.add(SIGQUIT).unwrap();
-        set.add(SIGILL).unwrap();
-        set.add(SIGABRT).unwrap();
-        set.add(SIGFPE).unwrap();
-        set.add(SIGKILL).unwrap();
-        set.add(SIGSEGV).unwrap();
-        set.add(SIGPIPE).unwrap();
-        set.add(SIGALRM).unwrap();
-        set.add(SIGTERM).unwrap();
-        set.add(SIGUSR1).unwrap();
-        set.add(SIGUSR2).unwrap();
-        set.add(SIGCHLD).unwrap();
-        set.add(SIGCONT).unwrap();
-        set.add(SIGSTOP).unwrap();
-        set.add(SIGTSTP).unwrap();
-        set.add(SIGTTIN).unwrap();
-        set.add(SIGTTOU).unwrap();
-        set.add(SIGBUS).unwrap();
-        set.add(SIGPROF).unwrap();
-        set.add(SIGSYS).unwrap();
-        set.add(SIGTRAP).unwrap();
-        set.add(SIGURG).unwrap();
-        set.add(SIGVTALRM,).unwrap();
-        set.add(SIGXCPU).unwrap();
-        set.add(SIGXFSZ).unwrap();
-        set.add(SIGIO).unwrap();
-        set.add(SIGWINCH).unwrap();
-        set
-    }
-
-    fn add(&mut self, sig: Sig) -> io::Result<()> {
-        unsafe { ok_errno((), sigaddset(&mut self.0, sig)) }
-    }
-
-    fn wait(&mut self) -> io::Result<Sig> {
-        let mut sig: Sig = 0;
-        let errno = unsafe { sigwait(&mut self.0, &mut sig) };
-        ok_errno(sig, errno)
-    }
-
-    fn thread_block_signals(&self) -> io::Result<()> {
-        let ecode = unsafe {
-            pthread_sigmask(SIG_BLOCK, &self.0, ptr::null_mut())
-        };
-        ok_errno((), ecode)
-    }
-
-    fn thread_set_signal_mask(&self) -> io::Result<()> {
-        let ecode = unsafe {
-            pthread_sigmask(SIG_SETMASK, &self.0, ptr::null_mut())
-        };
-        ok_errno((), ecode)
-    }
-}
-
-fn ok_errno<T>(ok: T, ecode: libc::c_int) -> io::Result<T> {
-    if ecode != 0 { Err(io::Error::from_raw_os_error(ecode)) } else { Ok(ok) }
-}
-
-extern {
-    fn sigwait(set: *mut sigset_t, sig: *mut Sig) -> Sig;
-    fn sigaddset(set: *mut sigset_t, sig: Sig) -> libc::c_int;
-    fn sigemptyset(set: *mut sigset_t) -> libc::c_int;
-    fn pthread_sigmask(
-        how: libc::c_int,
-        set: *const sigset_t,
-        oldset: *mut sigset_t,
-    ) -> libc::c_int;
-}
-
-// Most of this was lifted out of rust-lang:rust/src/libstd/sys/unix/c.rs.
-
-#[cfg(all(target_os = "linux", target_pointer_width = "32"))]
-#[repr(C)]
-struct sigset_t {
-    __val: [libc::c_ulong; 32],
-}
-
-#[cfg(all(target_os = "linux", target_pointer_width = "64"))]
-#[repr(C)]
-struct sigset_t {
-    __val: [libc::c_ulong; 16],
-}
-
-#[cfg(target_os = "android")]
-type sigset_t = libc::c_ulong;
-
-#[cfg(any(target_os = "macos", target_os = "ios"))]
-type sigset_t = u32;
-
-#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
-#[repr(C)]
-struct sigset_t {
-    bits: [u32; 4],
-}
-
-#[cfg(any(target_os = "bitrig", target_os = "netbsd", target_os = "openbsd"))]
-type sigset_t = libc::c_uint;
+// Everything else relies on POSIX signals. Other targets get a stub that
+// never receives any.
+#[cfg(unix)]
+pub use unix::*;
+#[cfg(not(unix))]
+pub use stub::*;
+
+mod signal;
+#[cfg(unix)]
+mod unix;
+#[cfg(not(unix))]
+mod stub;
@@ -0,0 +1,1234 @@
+// The signals themselves, and sets of them, which are the same on every
+// platform. Only the mapping to and from signal numbers differs: Unix
+// platforms use the numbers from `libc`, and targets without signals (see
+// `stub.rs`) use the ones Linux uses on x86, since they have none.
+
+use std::cmp;
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::iter;
+
+use bit_set::{self, BitSet};
+#[cfg(unix)]
+use libc::{
+    SIGHUP, SIGINT, SIGQUIT, SIGILL, SIGABRT, SIGFPE, SIGKILL,
+    SIGSEGV, SIGPIPE, SIGALRM, SIGTERM, SIGUSR1, SIGUSR2,
+    SIGCHLD, SIGCONT, SIGSTOP, SIGTSTP, SIGTTIN, SIGTTOU,
+    SIGBUS, SIGPROF, SIGSYS, SIGTRAP, SIGURG, SIGVTALRM,
+    SIGXCPU, SIGXFSZ, SIGWINCH,
+};
+#[cfg(all(unix, not(target_os = "haiku")))]
+use libc::SIGIO;
+// Haiku only has the System V name, which is the same signal.
+#[cfg(target_os = "haiku")]
+use libc::SIGPOLL as SIGIO;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use libc::SIGPWR;
+// Linux has no SIGSTKFLT on MIPS and SPARC.
+#[cfg(all(
+    any(target_os = "linux", target_os = "android"),
+    not(any(target_arch = "mips", target_arch = "mips32r6",
+            target_arch = "mips64", target_arch = "mips64r6",
+            target_arch = "sparc", target_arch = "sparc64")),
+))]
+use libc::SIGSTKFLT;
+#[cfg(any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+))]
+use libc::{SIGINFO, SIGEMT};
+
+pub(crate) type Sig = libc::c_int;
+
+/// A signal, which is either one this crate knows by name, a realtime
+/// signal or a signal given by its platform specific number.
+///
+/// Subscribing to a signal, e.g., with `notify_on` or `notify`, blocks it
+/// in every thread. Other signals are left alone, unless they are blocked
+/// explicitly, e.g., with `block` or `block_all_subscribable`. See
+/// `Signal::is_catchable` and `Signal::is_fault` for the signals that can't
+/// be subscribed to.
+///
+/// A few signals only exist on some platforms: `PWR` is only available on
+/// Linux and Android, and so is `STKFLT`, except on MIPS and SPARC. `INFO`
+/// and `EMT` are only available on macOS, iOS, FreeBSD, DragonFly BSD,
+/// NetBSD and OpenBSD. Using them elsewhere is a compile error, so code
+/// that handles them must be behind the same `#[cfg]`.
+///
+/// On targets without signals, e.g., Windows, only the signals that exist
+/// everywhere are available, and none of them is ever received. They use
+/// the numbers Linux uses on x86, e.g., for `as_raw` and `exit_code`.
+///
+/// More signals may be added in the future, so a `match` on a `Signal`
+/// outside of this crate needs a wildcard arm, even if it lists every
+/// variant:
+///
+/// ```compile_fail
+/// use chan_signal::Signal;
+///
+/// fn describe(sig: Signal) -> &'static str {
+///     match sig {
+///         Signal::HUP | Signal::INT | Signal::QUIT | Signal::ILL
+///         | Signal::ABRT | Signal::FPE | Signal::KILL | Signal::SEGV
+///         | Signal::PIPE | Signal::ALRM | Signal::TERM | Signal::USR1
+///         | Signal::USR2 | Signal::CHLD | Signal::CONT | Signal::STOP
+///         | Signal::TSTP | Signal::TTIN | Signal::TTOU | Signal::BUS
+///         | Signal::PROF | Signal::SYS | Signal::TRAP | Signal::URG
+///         | Signal::VTALRM | Signal::XCPU | Signal::XFSZ | Signal::IO
+///         | Signal::WINCH => "named",
+///         Signal::RT(_) => "realtime",
+///         Signal::Other(_) => "other",
+///     }
+/// }
+/// ```
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum Signal {
+    HUP,
+    INT,
+    QUIT,
+    ILL,
+    ABRT,
+    FPE,
+    KILL,
+    SEGV,
+    PIPE,
+    ALRM,
+    TERM,
+    USR1,
+    USR2,
+    CHLD,
+    CONT,
+    STOP,
+    TSTP,
+    TTIN,
+    TTOU,
+    BUS,
+    PROF,
+    SYS,
+    TRAP,
+    URG,
+    VTALRM,
+    XCPU,
+    XFSZ,
+    IO,
+    WINCH,
+    /// Power failure. Only available on Linux and Android.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    PWR,
+    /// Stack fault on a coprocessor. Only available on Linux and Android,
+    /// except on MIPS and SPARC.
+    #[cfg(all(
+        any(target_os = "linux", target_os = "android"),
+        not(any(target_arch = "mips", target_arch = "mips32r6",
+                target_arch = "mips64", target_arch = "mips64r6",
+                target_arch = "sparc", target_arch = "sparc64")),
+    ))]
+    STKFLT,
+    /// Status request from the keyboard (usually `^T`). Only available on
+    /// macOS and the BSDs.
+    #[cfg(any(
+        target_os = "macos", target_os = "ios",
+        target_os = "freebsd", target_os = "dragonfly",
+        target_os = "netbsd", target_os = "openbsd",
+    ))]
+    INFO,
+    /// Emulator trap. Only available on macOS and the BSDs.
+    #[cfg(any(
+        target_os = "macos", target_os = "ios",
+        target_os = "freebsd", target_os = "dragonfly",
+        target_os = "netbsd", target_os = "openbsd",
+    ))]
+    EMT,
+    /// A realtime signal, given as an offset from `SIGRTMIN`.
+    ///
+    /// Realtime signals are queued instead of coalesced. Since the range of
+    /// realtime signals is only known at runtime, use `Signal::rt` to create
+    /// one, which checks that the offset is in range. Realtime signals are
+    /// only supported on Linux and Android.
+    RT(u8),
+    /// A signal that this crate doesn't know about, with its platform
+    /// specific number.
+    ///
+    /// An unknown signal is never subscribable, but it may show up if,
+    /// e.g., a `SignalSet` is built from raw signal numbers.
+    ///
+    /// This crate never returns an `Other` signal whose number it knows.
+    /// If one is constructed anyway, e.g., `Signal::Other(libc::SIGINT)`,
+    /// it is treated exactly like the named (or realtime) variant with the
+    /// same number: it compares equal to it, hashes and orders the same
+    /// way, and has the same name.
+    Other(i32),
+}
+
+impl Signal {
+    /// Every signal supported by this crate, in the order they are declared.
+    ///
+    /// Except for `KILL` and `STOP`, which can't be caught or blocked, and
+    /// the fault signals (see `is_fault`), this is also precisely the set of
+    /// signals that is blocked by `block_all_subscribable`.
+    pub const ALL: &'static [Signal] = &[
+        Signal::HUP, Signal::INT, Signal::QUIT, Signal::ILL, Signal::ABRT,
+        Signal::FPE, Signal::KILL, Signal::SEGV, Signal::PIPE, Signal::ALRM,
+        Signal::TERM, Signal::USR1, Signal::USR2, Signal::CHLD, Signal::CONT,
+        Signal::STOP, Signal::TSTP, Signal::TTIN, Signal::TTOU, Signal::BUS,
+        Signal::PROF, Signal::SYS, Signal::TRAP, Signal::URG, Signal::VTALRM,
+        Signal::XCPU, Signal::XFSZ, Signal::IO, Signal::WINCH,
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        Signal::PWR,
+        #[cfg(all(
+            any(target_os = "linux", target_os = "android"),
+            not(any(target_arch = "mips", target_arch = "mips32r6",
+                    target_arch = "mips64", target_arch = "mips64r6",
+                    target_arch = "sparc", target_arch = "sparc64")),
+        ))]
+        Signal::STKFLT,
+        #[cfg(any(
+            target_os = "macos", target_os = "ios",
+            target_os = "freebsd", target_os = "dragonfly",
+            target_os = "netbsd", target_os = "openbsd",
+        ))]
+        Signal::INFO,
+        #[cfg(any(
+            target_os = "macos", target_os = "ios",
+            target_os = "freebsd", target_os = "dragonfly",
+            target_os = "netbsd", target_os = "openbsd",
+        ))]
+        Signal::EMT,
+    ];
+
+    /// Returns an iterator over every signal in `Signal::ALL`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chan_signal::Signal;
+    ///
+    /// let catchable: Vec<Signal> =
+    ///     Signal::iter().filter(|sig| sig.is_catchable()).collect();
+    /// assert!(!catchable.contains(&Signal::KILL));
+    /// ```
+    pub fn iter() -> impl Iterator<Item=Signal> {
+        Signal::ALL.iter().cloned()
+    }
+
+    /// Returns false if this signal can't be caught, blocked or ignored,
+    /// i.e., it is `KILL` or `STOP`.
+    pub fn is_catchable(self) -> bool {
+        !matches!(self.canonical(), Signal::KILL | Signal::STOP)
+    }
+
+    /// Returns true if this signal is raised synchronously by a fault in the
+    /// thread that receives it, i.e., it is `SEGV`, `BUS`, `ILL` or `FPE`.
+    ///
+    /// These signals can't be subscribed to. A fault can only be handled by
+    /// the thread that caused it, so it would never reach the watcher
+    /// thread, and blocking these signals makes the kernel kill the process
+    /// without running any handler. That includes the `SEGV` handler the
+    /// standard library installs to report stack overflows. So these are
+    /// never blocked by `block_all_subscribable` either.
+    pub fn is_fault(self) -> bool {
+        matches!(
+            self.canonical(),
+            Signal::SEGV | Signal::BUS | Signal::ILL | Signal::FPE
+        )
+    }
+
+    /// Returns true if this is a job control signal, i.e., one of `STOP`,
+    /// `TSTP`, `TTIN`, `TTOU` or `CONT`.
+    pub fn is_job_control(self) -> bool {
+        matches!(
+            self.canonical(),
+            Signal::STOP | Signal::TSTP | Signal::TTIN | Signal::TTOU
+            | Signal::CONT
+        )
+    }
+
+    /// Create a realtime signal, i.e., `SIGRTMIN + offset`.
+    ///
+    /// An error is returned if the offset is beyond `SIGRTMAX`, or if
+    /// realtime signals aren't supported on this platform.
+    pub fn rt(offset: u8) -> io::Result<Signal> {
+        let (min, max) = match realtime_range() {
+            None => return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "realtime signals are not supported on this platform",
+            )),
+            Some(range) => range,
+        };
+        if offset as Sig > max - min {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("SIGRTMIN+{} is beyond SIGRTMAX", offset),
+            ));
+        }
+        Ok(Signal::RT(offset))
+    }
+
+    /// Returns an identifier for this signal that is the same on every
+    /// platform, unlike its number (see `as_raw`).
+    ///
+    /// This is useful for sending signals over the wire in a compact form.
+    /// The mapping is guaranteed to never change:
+    ///
+    /// * Each signal that exists on every platform is its index in
+    ///   `Signal::ALL`, i.e., `HUP` is `0` and `WINCH` is `28`.
+    /// * The signals that only exist on some platforms are `29` (`PWR`),
+    ///   `30` (`STKFLT`), `31` (`INFO`) and `32` (`EMT`).
+    /// * Signals added in the future get the next unused ids.
+    /// * `RT(offset)` is `128 + offset`.
+    /// * Every other signal, including realtime signals with an offset
+    ///   larger than `126`, is `255`.
+    ///
+    /// Use `Signal::from_stable_id` to convert an id back into a signal.
+    pub fn stable_id(self) -> u8 {
+        match self.canonical() {
+            Signal::RT(offset) if offset < 127 => 128 + offset,
+            Signal::RT(_) | Signal::Other(_) => 255,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Signal::PWR => 29,
+            #[cfg(all(
+                any(target_os = "linux", target_os = "android"),
+                not(any(target_arch = "mips", target_arch = "mips32r6",
+                        target_arch = "mips64", target_arch = "mips64r6",
+                        target_arch = "sparc", target_arch = "sparc64")),
+            ))]
+            Signal::STKFLT => 30,
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::INFO => 31,
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::EMT => 32,
+            sig => Signal::ALL.iter().position(|&s| s == sig).unwrap() as u8,
+        }
+    }
+
+    /// Convert an identifier returned by `Signal::stable_id` back into a
+    /// signal.
+    ///
+    /// Returns `None` if no signal has the given id, which is always the
+    /// case for `255`.
+    pub fn from_stable_id(id: u8) -> Option<Signal> {
+        match id {
+            255 => None,
+            128..=254 => Some(Signal::RT(id - 128)),
+            _ => Signal::iter().find(|sig| sig.stable_id() == id),
+        }
+    }
+
+    /// Returns the key that signals are ordered by. Different signals
+    /// always have different keys.
+    fn order_key(self) -> (Sig, u8, i32) {
+        match self.canonical() {
+            Signal::RT(offset) => (self.as_sig(), 1, offset as i32),
+            Signal::Other(sig) => (sig, 2, sig),
+            _ => (self.as_sig(), 0, 0),
+        }
+    }
+
+    /// Returns the named or realtime variant for an `Other` signal whose
+    /// number this crate knows about, and the signal itself otherwise.
+    pub(crate) fn canonical(self) -> Signal {
+        match self {
+            Signal::Other(sig) => Signal::new(sig),
+            _ => self,
+        }
+    }
+
+    pub(crate) fn new(sig: Sig) -> Signal {
+        Signal::from_sig(sig).unwrap_or(Signal::Other(sig))
+    }
+
+    #[cfg(unix)]
+    fn from_sig(sig: Sig) -> Option<Signal> {
+        if let Some((min, max)) = realtime_range() {
+            if min <= sig && sig <= max {
+                return Some(Signal::RT((sig - min) as u8));
+            }
+        }
+        Some(match sig {
+            SIGHUP => Signal::HUP,
+            SIGINT => Signal::INT,
+            SIGQUIT => Signal::QUIT,
+            SIGILL => Signal::ILL,
+            SIGABRT => Signal::ABRT,
+            SIGFPE => Signal::FPE,
+            SIGKILL => Signal::KILL,
+            SIGSEGV => Signal::SEGV,
+            SIGPIPE => Signal::PIPE,
+            SIGALRM => Signal::ALRM,
+            SIGTERM => Signal::TERM,
+            SIGUSR1 => Signal::USR1,
+            SIGUSR2 => Signal::USR2,
+            SIGCHLD => Signal::CHLD,
+            SIGCONT => Signal::CONT,
+            SIGSTOP => Signal::STOP,
+            SIGTSTP => Signal::TSTP,
+            SIGTTIN => Signal::TTIN,
+            SIGTTOU => Signal::TTOU,
+            SIGBUS => Signal::BUS,
+            SIGPROF => Signal::PROF,
+            SIGSYS => Signal::SYS,
+            SIGTRAP => Signal::TRAP,
+            SIGURG => Signal::URG,
+            SIGVTALRM => Signal::VTALRM,
+            SIGXCPU => Signal::XCPU,
+            SIGXFSZ => Signal::XFSZ,
+            SIGIO => Signal::IO,
+            SIGWINCH => Signal::WINCH,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            SIGPWR => Signal::PWR,
+            #[cfg(all(
+                any(target_os = "linux", target_os = "android"),
+                not(any(target_arch = "mips", target_arch = "mips32r6",
+                        target_arch = "mips64", target_arch = "mips64r6",
+                        target_arch = "sparc", target_arch = "sparc64")),
+            ))]
+            SIGSTKFLT => Signal::STKFLT,
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            SIGINFO => Signal::INFO,
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            SIGEMT => Signal::EMT,
+            _ => return None,
+        })
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn as_sig(self) -> Sig {
+        match self {
+            Signal::HUP => SIGHUP,
+            Signal::INT => SIGINT,
+            Signal::QUIT => SIGQUIT,
+            Signal::ILL => SIGILL,
+            Signal::ABRT => SIGABRT,
+            Signal::FPE => SIGFPE,
+            Signal::KILL => SIGKILL,
+            Signal::SEGV => SIGSEGV,
+            Signal::PIPE => SIGPIPE,
+            Signal::ALRM => SIGALRM,
+            Signal::TERM => SIGTERM,
+            Signal::USR1 => SIGUSR1,
+            Signal::USR2 => SIGUSR2,
+            Signal::CHLD => SIGCHLD,
+            Signal::CONT => SIGCONT,
+            Signal::STOP => SIGSTOP,
+            Signal::TSTP => SIGTSTP,
+            Signal::TTIN => SIGTTIN,
+            Signal::TTOU => SIGTTOU,
+            Signal::BUS => SIGBUS,
+            Signal::PROF => SIGPROF,
+            Signal::SYS => SIGSYS,
+            Signal::TRAP => SIGTRAP,
+            Signal::URG => SIGURG,
+            Signal::VTALRM => SIGVTALRM,
+            Signal::XCPU => SIGXCPU,
+            Signal::XFSZ => SIGXFSZ,
+            Signal::IO => SIGIO,
+            Signal::WINCH => SIGWINCH,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Signal::PWR => SIGPWR,
+            #[cfg(all(
+                any(target_os = "linux", target_os = "android"),
+                not(any(target_arch = "mips", target_arch = "mips32r6",
+                        target_arch = "mips64", target_arch = "mips64r6",
+                        target_arch = "sparc", target_arch = "sparc64")),
+            ))]
+            Signal::STKFLT => SIGSTKFLT,
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::INFO => SIGINFO,
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::EMT => SIGEMT,
+            Signal::RT(offset) => match realtime_range() {
+                Some((min, _)) => min + offset as Sig,
+                // Not a valid signal number, so using it results in an
+                // error.
+                None => -1,
+            },
+            Signal::Other(sig) => sig,
+        }
+    }
+
+    // Without signals there are no numbers either, so these are the ones
+    // Linux uses on x86.
+
+    #[cfg(not(unix))]
+    fn from_sig(sig: Sig) -> Option<Signal> {
+        Signal::iter().find(|s| s.as_sig() == sig)
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn as_sig(self) -> Sig {
+        match self {
+            Signal::HUP => 1,
+            Signal::INT => 2,
+            Signal::QUIT => 3,
+            Signal::ILL => 4,
+            Signal::TRAP => 5,
+            Signal::ABRT => 6,
+            Signal::BUS => 7,
+            Signal::FPE => 8,
+            Signal::KILL => 9,
+            Signal::USR1 => 10,
+            Signal::SEGV => 11,
+            Signal::USR2 => 12,
+            Signal::PIPE => 13,
+            Signal::ALRM => 14,
+            Signal::TERM => 15,
+            Signal::CHLD => 17,
+            Signal::CONT => 18,
+            Signal::STOP => 19,
+            Signal::TSTP => 20,
+            Signal::TTIN => 21,
+            Signal::TTOU => 22,
+            Signal::URG => 23,
+            Signal::XCPU => 24,
+            Signal::XFSZ => 25,
+            Signal::VTALRM => 26,
+            Signal::PROF => 27,
+            Signal::WINCH => 28,
+            Signal::IO => 29,
+            Signal::SYS => 31,
+            // There are no realtime signals, so this isn't a valid number.
+            Signal::RT(_) => -1,
+            Signal::Other(sig) => sig,
+        }
+    }
+
+    /// Returns the platform specific number of this signal, e.g., `2` for
+    /// `INT` on Linux.
+    ///
+    /// Use `Signal::try_from` to convert a number back into a `Signal`.
+    pub fn as_raw(self) -> i32 {
+        self.as_sig()
+    }
+
+    /// Returns the exit status that shells report for a process killed by
+    /// this signal, i.e., `128` plus the signal number.
+    ///
+    /// This is useful with `std::process::exit` when re-raising the signal
+    /// (see `reraise_default`) isn't an option.
+    pub fn exit_code(self) -> i32 {
+        128 + self.as_raw()
+    }
+
+    /// Returns what happens to a process that receives this signal while
+    /// its disposition is `SIG_DFL`.
+    pub fn default_action(self) -> DefaultAction {
+        use DefaultAction::*;
+
+        match self.canonical() {
+            Signal::HUP => Term,
+            Signal::INT => Term,
+            Signal::QUIT => Core,
+            Signal::ILL => Core,
+            Signal::ABRT => Core,
+            Signal::FPE => Core,
+            Signal::KILL => Term,
+            Signal::SEGV => Core,
+            Signal::PIPE => Term,
+            Signal::ALRM => Term,
+            Signal::TERM => Term,
+            Signal::USR1 => Term,
+            Signal::USR2 => Term,
+            Signal::CHLD => Ignore,
+            Signal::CONT => Cont,
+            Signal::STOP => Stop,
+            Signal::TSTP => Stop,
+            Signal::TTIN => Stop,
+            Signal::TTOU => Stop,
+            Signal::BUS => Core,
+            Signal::PROF => Term,
+            Signal::SYS => Core,
+            Signal::TRAP => Core,
+            Signal::URG => Ignore,
+            Signal::VTALRM => Term,
+            Signal::XCPU => Core,
+            Signal::XFSZ => Core,
+            Signal::IO => io_default_action(),
+            Signal::WINCH => Ignore,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Signal::PWR => Term,
+            #[cfg(all(
+                any(target_os = "linux", target_os = "android"),
+                not(any(target_arch = "mips", target_arch = "mips32r6",
+                        target_arch = "mips64", target_arch = "mips64r6",
+                        target_arch = "sparc", target_arch = "sparc64")),
+            ))]
+            Signal::STKFLT => Term,
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::INFO => Ignore,
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::EMT => Core,
+            // This is true for most signals that we don't know about, e.g.,
+            // realtime signals.
+            Signal::RT(_) | Signal::Other(_) => Term,
+        }
+    }
+
+    /// Returns the conventional name of this signal, e.g., `SIGINT`.
+    ///
+    /// This is also what the `Display` impl writes, except for `RT` and
+    /// `Other` signals, whose names are `SIGRTMIN` and `SIGUNKNOWN`. Those
+    /// are displayed as, e.g., `SIGRTMIN+3` and `signal 100` instead.
+    pub fn name(self) -> &'static str {
+        match self.canonical() {
+            Signal::HUP => "SIGHUP",
+            Signal::INT => "SIGINT",
+            Signal::QUIT => "SIGQUIT",
+            Signal::ILL => "SIGILL",
+            Signal::ABRT => "SIGABRT",
+            Signal::FPE => "SIGFPE",
+            Signal::KILL => "SIGKILL",
+            Signal::SEGV => "SIGSEGV",
+            Signal::PIPE => "SIGPIPE",
+            Signal::ALRM => "SIGALRM",
+            Signal::TERM => "SIGTERM",
+            Signal::USR1 => "SIGUSR1",
+            Signal::USR2 => "SIGUSR2",
+            Signal::CHLD => "SIGCHLD",
+            Signal::CONT => "SIGCONT",
+            Signal::STOP => "SIGSTOP",
+            Signal::TSTP => "SIGTSTP",
+            Signal::TTIN => "SIGTTIN",
+            Signal::TTOU => "SIGTTOU",
+            Signal::BUS => "SIGBUS",
+            Signal::PROF => "SIGPROF",
+            Signal::SYS => "SIGSYS",
+            Signal::TRAP => "SIGTRAP",
+            Signal::URG => "SIGURG",
+            Signal::VTALRM => "SIGVTALRM",
+            Signal::XCPU => "SIGXCPU",
+            Signal::XFSZ => "SIGXFSZ",
+            Signal::IO => "SIGIO",
+            Signal::WINCH => "SIGWINCH",
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Signal::PWR => "SIGPWR",
+            #[cfg(all(
+                any(target_os = "linux", target_os = "android"),
+                not(any(target_arch = "mips", target_arch = "mips32r6",
+                        target_arch = "mips64", target_arch = "mips64r6",
+                        target_arch = "sparc", target_arch = "sparc64")),
+            ))]
+            Signal::STKFLT => "SIGSTKFLT",
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::INFO => "SIGINFO",
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::EMT => "SIGEMT",
+            Signal::RT(_) => "SIGRTMIN",
+            Signal::Other(_) => "SIGUNKNOWN",
+        }
+    }
+
+    /// Returns a human readable description of this signal, e.g.,
+    /// `Interrupt` for `INT`.
+    ///
+    /// The descriptions are the same as the ones used by glibc's
+    /// `strsignal`. Unlike `strsignal`, they don't depend on the platform or
+    /// the locale.
+    pub fn description(self) -> &'static str {
+        match self.canonical() {
+            Signal::HUP => "Hangup",
+            Signal::INT => "Interrupt",
+            Signal::QUIT => "Quit",
+            Signal::ILL => "Illegal instruction",
+            Signal::ABRT => "Aborted",
+            Signal::FPE => "Floating point exception",
+            Signal::KILL => "Killed",
+            Signal::SEGV => "Segmentation fault",
+            Signal::PIPE => "Broken pipe",
+            Signal::ALRM => "Alarm clock",
+            Signal::TERM => "Terminated",
+            Signal::USR1 => "User defined signal 1",
+            Signal::USR2 => "User defined signal 2",
+            Signal::CHLD => "Child exited",
+            Signal::CONT => "Continued",
+            Signal::STOP => "Stopped (signal)",
+            Signal::TSTP => "Stopped",
+            Signal::TTIN => "Stopped (tty input)",
+            Signal::TTOU => "Stopped (tty output)",
+            Signal::BUS => "Bus error",
+            Signal::PROF => "Profiling timer expired",
+            Signal::SYS => "Bad system call",
+            Signal::TRAP => "Trace/breakpoint trap",
+            Signal::URG => "Urgent I/O condition",
+            Signal::VTALRM => "Virtual timer expired",
+            Signal::XCPU => "CPU time limit exceeded",
+            Signal::XFSZ => "File size limit exceeded",
+            Signal::IO => "I/O possible",
+            Signal::WINCH => "Window changed",
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Signal::PWR => "Power failure",
+            #[cfg(all(
+                any(target_os = "linux", target_os = "android"),
+                not(any(target_arch = "mips", target_arch = "mips32r6",
+                        target_arch = "mips64", target_arch = "mips64r6",
+                        target_arch = "sparc", target_arch = "sparc64")),
+            ))]
+            Signal::STKFLT => "Stack fault",
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::INFO => "Information request",
+            #[cfg(any(
+                target_os = "macos", target_os = "ios",
+                target_os = "freebsd", target_os = "dragonfly",
+                target_os = "netbsd", target_os = "openbsd",
+            ))]
+            Signal::EMT => "EMT trap",
+            Signal::RT(_) => "Real-time signal",
+            Signal::Other(_) => "Unknown signal",
+        }
+    }
+}
+
+impl TryFrom<i32> for Signal {
+    type Error = UnknownSignal;
+
+    /// Convert a platform specific signal number into a `Signal`.
+    ///
+    /// An error is returned if the number doesn't correspond to any signal
+    /// supported by this crate.
+    fn try_from(sig: i32) -> Result<Signal, UnknownSignal> {
+        Signal::from_sig(sig).ok_or(UnknownSignal { number: sig })
+    }
+}
+
+impl From<Signal> for i32 {
+    /// Returns the platform specific number of the signal.
+    ///
+    /// This is the same as `Signal::as_raw`.
+    fn from(sig: Signal) -> i32 {
+        sig.as_raw()
+    }
+}
+
+// Signals are (de)serialized by name, e.g., `"TERM"` or `"RTMIN+3"`, since
+// numbers differ between platforms. For the same reason, `Other` signals
+// can't be serialized.
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Signal {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if let Signal::Other(sig) = self.canonical() {
+            return Err(serde::ser::Error::custom(format!(
+                "cannot serialize unknown signal {}", sig,
+            )));
+        }
+        if let Signal::RT(offset) = self.canonical() {
+            return serializer.serialize_str(&format!("RTMIN+{}", offset));
+        }
+        serializer.serialize_str(&self.name()[3..])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Signal {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Signal, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Signal;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a signal name such as \"TERM\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                name: &str,
+            ) -> Result<Signal, E> {
+                use serde::de::Unexpected;
+
+                if let Some(offset) = name.strip_prefix("RTMIN+") {
+                    return match offset.parse() {
+                        Ok(offset) => Ok(Signal::RT(offset)),
+                        Err(_) => {
+                            Err(E::invalid_value(Unexpected::Str(name), &self))
+                        }
+                    };
+                }
+                match Signal::iter().find(|sig| &sig.name()[3..] == name) {
+                    Some(sig) => Ok(sig),
+                    None => {
+                        Err(E::invalid_value(Unexpected::Str(name), &self))
+                    }
+                }
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+/// The error returned when converting an unsupported signal number into a
+/// `Signal`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnknownSignal {
+    number: i32,
+}
+
+impl UnknownSignal {
+    /// Returns the signal number that couldn't be converted.
+    pub fn number(&self) -> i32 {
+        self.number
+    }
+}
+
+impl fmt::Display for UnknownSignal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unsupported signal number: {}", self.number)
+    }
+}
+
+impl error::Error for UnknownSignal {}
+
+/// The reason an operation on signals failed.
+///
+/// The functions in this crate that return an `io::Error` wrap one of these
+/// in it when the failure is specific to signals (on targets without
+/// signals, that is always `Unsupported`). It can be recovered with
+/// `io::Error::get_ref` and `downcast_ref`:
+///
+/// ```
+/// use chan_signal::{Signal, SignalError};
+///
+/// let err = chan_signal::try_notify(&[Signal::KILL]).unwrap_err();
+/// match err.get_ref().and_then(|e| e.downcast_ref::<SignalError>()) {
+///     Some(&SignalError::Uncatchable(sig)) => assert_eq!(sig, Signal::KILL),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SignalError {
+    /// Changing or reading the signal mask of the calling thread failed.
+    MaskFailed(io::Error),
+    /// Waiting for signals in the watcher thread failed.
+    WaitFailed(io::Error),
+    /// The signal isn't supported on this platform.
+    Unsupported(Signal),
+    /// The signal can't be caught or blocked, i.e., it is `KILL` or `STOP`.
+    Uncatchable(Signal),
+    /// The signal is raised by faults, so it can't be subscribed to. See
+    /// `Signal::is_fault`.
+    Fault(Signal),
+    /// The signal was excluded with `set_excluded`, so it can't be
+    /// subscribed to or blocked.
+    Excluded(Signal),
+    /// This many threads were already running when this crate first
+    /// blocked signals. See `deny_existing_threads`.
+    ThreadsAlreadySpawned(usize),
+}
+
+impl fmt::Display for SignalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SignalError::MaskFailed(ref err) => {
+                write!(f, "failed to change the signal mask: {}", err)
+            }
+            SignalError::WaitFailed(ref err) => {
+                write!(f, "failed to wait for signals: {}", err)
+            }
+            SignalError::Unsupported(sig) => {
+                write!(f, "{} is not supported on this platform", sig)
+            }
+            SignalError::Uncatchable(sig) => {
+                write!(f, "{} cannot be caught or blocked", sig)
+            }
+            SignalError::Fault(sig) => {
+                write!(f, "{} is raised by faults and cannot be subscribed \
+                           to", sig)
+            }
+            SignalError::Excluded(sig) => {
+                write!(f, "{} is excluded and cannot be blocked", sig)
+            }
+            SignalError::ThreadsAlreadySpawned(n) => {
+                write!(f, "{} threads were already running when signals \
+                           were first blocked; block signals before \
+                           spawning threads", n)
+            }
+        }
+    }
+}
+
+impl error::Error for SignalError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            SignalError::MaskFailed(ref err)
+            | SignalError::WaitFailed(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<SignalError> for io::Error {
+    fn from(err: SignalError) -> io::Error {
+        let kind = match err {
+            SignalError::MaskFailed(ref err)
+            | SignalError::WaitFailed(ref err) => err.kind(),
+            SignalError::ThreadsAlreadySpawned(_) => io::ErrorKind::Other,
+            _ => io::ErrorKind::InvalidInput,
+        };
+        io::Error::new(kind, err)
+    }
+}
+
+/// Signals are ordered by their platform specific number, which is not
+/// necessarily the order in which they are declared. Signals with the same
+/// number (e.g., realtime signals on platforms without any, which have no
+/// valid number) are ordered by variant, and then by offset or number.
+impl Ord for Signal {
+    fn cmp(&self, other: &Signal) -> cmp::Ordering {
+        self.order_key().cmp(&other.order_key())
+    }
+}
+
+impl PartialOrd for Signal {
+    fn partial_cmp(&self, other: &Signal) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Signal {
+    fn eq(&self, other: &Signal) -> bool {
+        self.order_key() == other.order_key()
+    }
+}
+
+impl Eq for Signal {}
+
+impl Hash for Signal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.order_key().hash(state);
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.canonical() {
+            Signal::RT(0) => f.write_str("SIGRTMIN"),
+            Signal::RT(offset) => write!(f, "SIGRTMIN+{}", offset),
+            Signal::Other(sig) => write!(f, "signal {}", sig),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+/// The action taken when a signal with its default disposition is received.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DefaultAction {
+    /// Terminate the process.
+    Term,
+    /// Terminate the process and dump core.
+    Core,
+    /// Stop the process.
+    Stop,
+    /// Continue the process if it is stopped.
+    Cont,
+    /// Ignore the signal.
+    Ignore,
+}
+
+// `IO` is the same as the System V `POLL` on these, which terminates.
+// Redox's relibc and Fuchsia's musl based libc follow Linux here, and so do
+// targets without signals.
+#[cfg(any(
+    target_os = "linux", target_os = "android", target_os = "redox",
+    target_os = "fuchsia", target_os = "haiku",
+    target_os = "solaris", target_os = "illumos", not(unix)
+))]
+fn io_default_action() -> DefaultAction {
+    DefaultAction::Term
+}
+
+// BSD derived systems ignore `IO` by default.
+#[cfg(not(any(
+    target_os = "linux", target_os = "android", target_os = "redox",
+    target_os = "fuchsia", target_os = "haiku",
+    target_os = "solaris", target_os = "illumos", not(unix)
+)))]
+fn io_default_action() -> DefaultAction {
+    DefaultAction::Ignore
+}
+
+/// Returns the range of realtime signals, `SIGRTMIN..=SIGRTMAX`, if they are
+/// supported on this platform.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn realtime_range() -> Option<(Sig, Sig)> {
+    Some((libc::SIGRTMIN(), libc::SIGRTMAX()))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) fn realtime_range() -> Option<(Sig, Sig)> {
+    None
+}
+
+/// A set of signals.
+///
+/// Functions that subscribe to or block groups of signals, like `notify` and
+/// `block`, accept anything that can be converted into a `SignalSet`,
+/// including a slice of signals.
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::{Signal, SignalSet};
+///
+/// let mut signals = SignalSet::job_control();
+/// signals.insert(Signal::INT);
+/// let signal = chan_signal::notify(signals);
+/// ```
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct SignalSet(pub(crate) BitSet);
+
+impl SignalSet {
+    /// Create an empty set.
+    pub fn new() -> SignalSet {
+        SignalSet(BitSet::new())
+    }
+
+    /// Create a set of every signal in `Signal::ALL`.
+    pub fn all() -> SignalSet {
+        Signal::iter().collect()
+    }
+
+    /// Create a set of the signals that conventionally ask a process to
+    /// terminate: `HUP`, `INT`, `QUIT` and `TERM`.
+    ///
+    /// Unlike `fatal`, this doesn't include signals that report errors, like
+    /// `SEGV`, nor `KILL`, which can't be handled anyway.
+    pub fn termination() -> SignalSet {
+        SignalSet::from(&[
+            Signal::HUP, Signal::INT, Signal::QUIT, Signal::TERM,
+        ])
+    }
+
+    /// Create a set of every signal whose default action terminates the
+    /// process (with or without a core dump).
+    pub fn fatal() -> SignalSet {
+        Signal::iter().filter(|sig| {
+            matches!(
+                sig.default_action(),
+                DefaultAction::Term | DefaultAction::Core
+            )
+        }).collect()
+    }
+
+    /// Create a set of the job control signals: `STOP`, `TSTP`, `TTIN`,
+    /// `TTOU` and `CONT`.
+    pub fn job_control() -> SignalSet {
+        SignalSet::from(&[
+            Signal::STOP, Signal::TSTP, Signal::TTIN, Signal::TTOU,
+            Signal::CONT,
+        ])
+    }
+
+    /// Add a signal to this set. Returns true if it wasn't already present.
+    ///
+    /// A signal without a valid number (e.g., a realtime signal on a
+    /// platform without them) is never added.
+    pub fn insert(&mut self, signal: Signal) -> bool {
+        match set_index(signal) {
+            None => false,
+            Some(i) => self.0.insert(i),
+        }
+    }
+
+    /// Remove a signal from this set. Returns true if it was present.
+    pub fn remove(&mut self, signal: Signal) -> bool {
+        match set_index(signal) {
+            None => false,
+            Some(i) => self.0.remove(i),
+        }
+    }
+
+    /// Returns true if this set contains the given signal.
+    pub fn contains(&self, signal: Signal) -> bool {
+        match set_index(signal) {
+            None => false,
+            Some(i) => self.0.contains(i),
+        }
+    }
+
+    /// Returns the number of signals in this set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if this set contains no signals.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the set of signals in either `self` or `other`.
+    pub fn union(&self, other: &SignalSet) -> SignalSet {
+        SignalSet(self.0.union(&other.0).collect())
+    }
+
+    /// Returns the set of signals in both `self` and `other`.
+    pub fn intersection(&self, other: &SignalSet) -> SignalSet {
+        SignalSet(self.0.intersection(&other.0).collect())
+    }
+
+    /// Returns the set of signals in `self` but not in `other`.
+    pub fn difference(&self, other: &SignalSet) -> SignalSet {
+        SignalSet(self.0.difference(&other.0).collect())
+    }
+
+    /// Returns an iterator over the signals in this set, in ascending order
+    /// of signal number.
+    pub fn iter(&self) -> SignalSetIter<'_> {
+        SignalSetIter(self.0.iter())
+    }
+}
+
+/// Returns the index of `signal` in a `SignalSet`, or `None` if it doesn't
+/// have a valid signal number.
+fn set_index(signal: Signal) -> Option<usize> {
+    let sig = signal.as_sig();
+    if sig <= 0 { None } else { Some(sig as usize) }
+}
+
+impl fmt::Debug for SignalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IntoIterator for &'a SignalSet {
+    type Item = Signal;
+    type IntoIter = SignalSetIter<'a>;
+
+    fn into_iter(self) -> SignalSetIter<'a> {
+        self.iter()
+    }
+}
+
+impl iter::FromIterator<Signal> for SignalSet {
+    fn from_iter<I: IntoIterator<Item=Signal>>(it: I) -> SignalSet {
+        let mut set = SignalSet::new();
+        set.extend(it);
+        set
+    }
+}
+
+impl Extend<Signal> for SignalSet {
+    fn extend<I: IntoIterator<Item=Signal>>(&mut self, it: I) {
+        for signal in it {
+            self.insert(signal);
+        }
+    }
+}
+
+impl From<Signal> for SignalSet {
+    fn from(signal: Signal) -> SignalSet {
+        let mut set = SignalSet::new();
+        set.insert(signal);
+        set
+    }
+}
+
+impl<'a> From<&'a SignalSet> for SignalSet {
+    fn from(set: &'a SignalSet) -> SignalSet {
+        set.clone()
+    }
+}
+
+impl<'a> From<&'a [Signal]> for SignalSet {
+    fn from(signals: &'a [Signal]) -> SignalSet {
+        signals.iter().cloned().collect()
+    }
+}
+
+impl From<Vec<Signal>> for SignalSet {
+    fn from(signals: Vec<Signal>) -> SignalSet {
+        signals.into_iter().collect()
+    }
+}
+
+impl From<SignalSet> for Vec<Signal> {
+    fn from(signals: SignalSet) -> Vec<Signal> {
+        signals.iter().collect()
+    }
+}
+
+macro_rules! signal_set_from_array {
+    ($($n:expr),*) => {
+        $(
+            impl<'a> From<&'a [Signal; $n]> for SignalSet {
+                fn from(signals: &'a [Signal; $n]) -> SignalSet {
+                    signals.iter().cloned().collect()
+                }
+            }
+        )*
+    }
+}
+
+// Without these, `notify(&[Signal::INT])` wouldn't compile, since a
+// reference to an array isn't coerced to a slice for a generic parameter.
+signal_set_from_array!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
+    20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+);
+
+/// An iterator over the signals in a `SignalSet`.
+pub struct SignalSetIter<'a>(bit_set::Iter<'a, u32>);
+
+impl<'a> Iterator for SignalSetIter<'a> {
+    type Item = Signal;
+
+    fn next(&mut self) -> Option<Signal> {
+        self.0.next().map(|sig| Signal::new(sig as Sig))
+    }
+}
@@ -0,0 +1,1159 @@
+// A stub of the API for targets without POSIX signals. Every function and
+// type that exists on all Unix platforms exists here too, so that programs
+// that build for several targets can keep a single code path. The ones that
+// only exist on some of them, like `signal_fd` on Linux, don't. The signals
+// themselves are shared with Unix (see `signal.rs`). Nothing is ever received:
+// channels stay empty, functions that would change how signals are handled
+// return an error, and functions that report on signals report that nothing
+// happened. The fallible functions can be used to find out at runtime
+// whether signals are supported.
+//
+// The one exception is `kill_this` on Windows, which can send `INT` to the
+// console with `GenerateConsoleCtrlEvent`.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::hash::Hash;
+use std::io;
+use std::process;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::sync::{Arc, Mutex, Weak, mpsc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use chan::Sender;
+
+use signal::{Signal, SignalError, SignalSet};
+
+lazy_static! {
+    // The senders of the channels returned by `notify` and the like. They're
+    // kept so that the channels are never closed.
+    static ref SENDERS: Mutex<Vec<Box<dyn Any + Send>>> = Mutex::new(vec![]);
+}
+
+/// Create a new channel subscribed to the given signals.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything. It is never closed either.
+pub fn notify<S: Into<SignalSet>>(signals: S) -> chan::Receiver<Signal> {
+    let _ = signals;
+    never()
+}
+
+/// Create a new channel subscribed to the given signals.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn try_notify<S>(signals: S) -> io::Result<chan::Receiver<Signal>>
+        where S: Into<SignalSet> {
+    unsupported(signals)?;
+    Ok(never())
+}
+
+/// Create a new channel subscribed to the given signals, and return both of
+/// its ends.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything but what is sent on the sender returned.
+pub fn notify_channel<S>(
+    signals: S,
+) -> (chan::Sender<Signal>, chan::Receiver<Signal>)
+        where S: Into<SignalSet> {
+    let _ = signals;
+    let (s, r) = chan::sync(100);
+    keep(s.clone());
+    (s, r)
+}
+
+/// Receive a signal that has already been sent on a channel, without
+/// blocking.
+pub fn try_recv(chan: &chan::Receiver<Signal>) -> Option<Signal> {
+    let mut signal = None;
+    chan_select! {
+        default => {},
+        chan.recv() -> sig => signal = sig,
+    }
+    signal
+}
+
+/// Create a new channel subscribed to the job control signals.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_job_control() -> chan::Receiver<Signal> {
+    never()
+}
+
+/// Create a new channel subscribed to the given signals that tags each
+/// signal with the name of a group.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_group(
+    name: &str,
+    signals: &[Signal],
+) -> chan::Receiver<(String, Signal)> {
+    let _ = (name, signals);
+    never()
+}
+
+/// Create a new channel that reports whether a signal arrived once or twice
+/// in quick succession.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_double(
+    signal: Signal,
+    window: Duration,
+) -> chan::Receiver<DoublePress> {
+    let _ = (signal, window);
+    never()
+}
+
+/// An occurrence of a signal subscribed with `notify_double`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DoublePress {
+    /// The signal arrived for the first time, or long enough after the
+    /// last `First`.
+    First,
+    /// The signal arrived again soon after a `First`.
+    Second,
+}
+
+/// Create a new channel that receives the status of every child process
+/// that exits.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_children() -> chan::Receiver<ChildEvent> {
+    never()
+}
+
+/// A child process that exited. See `notify_children`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChildEvent {
+    pid: i32,
+    status: process::ExitStatus,
+}
+
+impl ChildEvent {
+    /// Returns the process id of the child.
+    pub fn pid(&self) -> i32 {
+        self.pid
+    }
+
+    /// Returns the child's exit status.
+    pub fn status(&self) -> process::ExitStatus {
+        self.status
+    }
+}
+
+/// Create a new channel that receives the size of the terminal whenever it
+/// changes.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_winch() -> chan::Receiver<(u16, u16)> {
+    never()
+}
+
+/// Create a new `std::sync::mpsc` channel subscribed to the given signals.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything. It is never closed either.
+pub fn notify_std(signals: &[Signal]) -> mpsc::Receiver<Signal> {
+    let _ = signals;
+    let (s, r) = mpsc::sync_channel(100);
+    keep(s);
+    r
+}
+
+/// Something that signals can be sent to without blocking.
+///
+/// See `notify_on_sink`.
+pub trait SignalSink {
+    /// Send a signal to this sink.
+    ///
+    /// This must not block. It returns false once the sink has nowhere left
+    /// to send signals.
+    fn try_send(&self, sig: Signal) -> bool;
+}
+
+impl SignalSink for Sender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        let chan = self;
+        chan_select! {
+            default => {},
+            chan.send(sig) => {},
+        }
+        true
+    }
+}
+
+impl SignalSink for Weak<Sender<Signal>> {
+    fn try_send(&self, sig: Signal) -> bool {
+        match self.upgrade() {
+            None => false,
+            Some(chan) => chan.try_send(sig),
+        }
+    }
+}
+
+impl SignalSink for mpsc::Sender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        self.send(sig).is_ok()
+    }
+}
+
+impl SignalSink for mpsc::SyncSender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        match mpsc::SyncSender::try_send(self, sig) {
+            Err(mpsc::TrySendError::Disconnected(_)) => false,
+            Ok(()) | Err(mpsc::TrySendError::Full(_)) => true,
+        }
+    }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl SignalSink for crossbeam_channel::Sender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        use crossbeam_channel::TrySendError;
+
+        match crossbeam_channel::Sender::try_send(self, sig) {
+            Err(TrySendError::Disconnected(_)) => false,
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl SignalSink for futures::sync::mpsc::UnboundedSender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        self.unbounded_send(sig).is_ok()
+    }
+}
+
+/// Subscribe to a signal on a sink.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn notify_on_sink<S>(sink: S, signal: Signal)
+        where S: SignalSink + Send + 'static {
+    let _ = (sink, signal);
+}
+
+/// Subscribe to a signal on a channel without keeping the channel alive.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn notify_on_weak(chan: &Arc<Sender<Signal>>, signal: Signal) {
+    let _ = (chan, signal);
+}
+
+/// Subscribe to a signal on a channel.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn notify_on(chan: &Sender<Signal>, signal: Signal) {
+    let _ = (chan, signal);
+}
+
+/// Subscribe to a signal on a channel.
+///
+/// Signals aren't supported on this platform, so this always returns
+/// `SignalError::Unsupported`.
+pub fn try_notify_on(chan: &Sender<Signal>, signal: Signal) -> io::Result<()> {
+    let _ = chan;
+    Err(SignalError::Unsupported(signal).into())
+}
+
+/// Subscribe to many signals on a channel at once.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn notify_on_many<S: Into<SignalSet>>(chan: &Sender<Signal>, signals: S) {
+    let _ = (chan, signals);
+}
+
+/// Subscribe to many signals on a channel at once.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn try_notify_on_many<S>(chan: &Sender<Signal>, signals: S)
+        -> io::Result<()>
+        where S: Into<SignalSet> {
+    let _ = chan;
+    unsupported(signals)
+}
+
+/// Create a new channel subscribed to the given signals that receives
+/// details about the delivery of each signal.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_events<S>(signals: S) -> chan::Receiver<SignalEvent>
+        where S: Into<SignalSet> {
+    let _ = signals;
+    never()
+}
+
+/// Subscribe to many signals on a channel that receives the value sent along
+/// with each signal.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn notify_events_on<S>(chan: &Sender<SignalEvent>, signals: S)
+        where S: Into<SignalSet> {
+    let _ = (chan, signals);
+}
+
+/// A signal along with details about its delivery.
+///
+/// See `notify_events`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignalEvent {
+    signal: Signal,
+    received_at: Instant,
+    seq: u64,
+    value: Option<i64>,
+}
+
+impl SignalEvent {
+    /// Returns the signal that arrived.
+    pub fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    /// Returns when the signal was received.
+    pub fn received_at(&self) -> Instant {
+        self.received_at
+    }
+
+    /// Returns the sequence number of this delivery.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Returns the value sent with the signal, if any.
+    pub fn value(&self) -> Option<i64> {
+        self.value
+    }
+}
+
+/// Create a new channel subscribed to the given signals that receives
+/// information about where each signal came from.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_info<S>(signals: S) -> chan::Receiver<SignalInfo>
+        where S: Into<SignalSet> {
+    let _ = signals;
+    never()
+}
+
+/// Subscribe to many signals on a channel that receives information about
+/// where each signal came from.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn notify_info_on<S>(chan: &Sender<SignalInfo>, signals: S)
+        where S: Into<SignalSet> {
+    let _ = (chan, signals);
+}
+
+/// Information about an arriving signal.
+///
+/// See `notify_info`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignalInfo {
+    signal: Signal,
+    code: i32,
+    pid: Option<i32>,
+    uid: Option<u32>,
+    value: Option<i64>,
+}
+
+impl SignalInfo {
+    /// Returns the signal that arrived.
+    pub fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    /// Returns the raw `si_code`, which says why the signal was sent.
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    /// Returns true if the signal was sent by the kernel.
+    pub fn is_from_kernel(&self) -> bool {
+        self.code > 0
+    }
+
+    /// Returns the process ID of the sender, if known.
+    pub fn pid(&self) -> Option<i32> {
+        self.pid
+    }
+
+    /// Returns the real user ID of the sender, if known.
+    pub fn uid(&self) -> Option<u32> {
+        self.uid
+    }
+
+    /// Returns the value sent with the signal, if any.
+    pub fn value(&self) -> Option<i64> {
+        self.value
+    }
+}
+
+/// Create a flag that is set when any of the given signals arrives.
+///
+/// Signals aren't supported on this platform, so the flag is never set.
+pub fn notify_flag(signals: &[Signal]) -> Arc<AtomicBool> {
+    let _ = signals;
+    Arc::new(AtomicBool::new(false))
+}
+
+/// Create a counter that is incremented every time the given signal
+/// arrives.
+///
+/// Signals aren't supported on this platform, so the counter stays at `0`.
+pub fn notify_counter(signal: Signal) -> Arc<AtomicUsize> {
+    let _ = signal;
+    Arc::new(AtomicUsize::new(0))
+}
+
+/// Call a closure whenever a signal arrives.
+///
+/// Signals aren't supported on this platform, so the closure is never
+/// called.
+pub fn on_signal<F>(signal: Signal, f: F)
+        where F: Fn(Signal) + Send + 'static {
+    let _ = (signal, f);
+}
+
+/// Call a closure whenever one of the given signals arrives, until it is
+/// removed with `remove_callback`.
+///
+/// Signals aren't supported on this platform, so the closure is never
+/// called.
+pub fn notify_fn<F>(signals: &[Signal], f: F) -> CallbackId
+        where F: FnMut(Signal) + Send + 'static {
+    let _ = (signals, f);
+    CallbackId(0)
+}
+
+/// Remove a closure registered with `notify_fn`.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn remove_callback(id: CallbackId) {
+    let _ = id;
+}
+
+/// Identifies a closure registered with `notify_fn`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CallbackId(usize);
+
+/// Create a new channel subscribed to the given signals, where a second
+/// occurrence of a signal gets its default action.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_with_escalation(signals: &[Signal]) -> chan::Receiver<Signal> {
+    notify(signals)
+}
+
+/// Forget that the given signals have already been delivered once.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn reset_escalation(signals: &[Signal]) {
+    let _ = signals;
+}
+
+/// Return the signal that was received most recently, if any.
+///
+/// Signals aren't supported on this platform, so this is always `None`.
+pub fn last_signal() -> Option<Signal> {
+    None
+}
+
+/// Return the most recently received signals, along with when they
+/// arrived.
+///
+/// Signals aren't supported on this platform, so this is always empty.
+pub fn recent_signals(n: usize) -> Vec<(Signal, SystemTime)> {
+    let _ = n;
+    vec![]
+}
+
+/// Return how many times each signal has been received, delivered and
+/// dropped.
+///
+/// Signals aren't supported on this platform, so every count is `0`.
+pub fn stats() -> SignalStats {
+    SignalStats::default()
+}
+
+/// A snapshot of how many times each signal has been received, delivered
+/// and dropped. See `stats`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SignalStats(BTreeMap<Signal, SignalCounts>);
+
+impl SignalStats {
+    /// Returns the counts for the given signal.
+    pub fn get(&self, signal: Signal) -> SignalCounts {
+        self.0.get(&signal).cloned().unwrap_or_default()
+    }
+
+    /// Returns the counts of every signal that has been received at least
+    /// once.
+    pub fn iter<'a>(&'a self)
+            -> impl Iterator<Item=(Signal, SignalCounts)> + 'a {
+        self.0.iter().map(|(&sig, &counts)| (sig, counts))
+    }
+}
+
+/// How many times a signal has been received, delivered and dropped.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SignalCounts {
+    received: u64,
+    delivered: u64,
+    dropped: u64,
+}
+
+impl SignalCounts {
+    /// Returns the number of times the signal was received.
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// Returns the number of times the signal was sent on a channel.
+    pub fn delivered(&self) -> u64 {
+        self.delivered
+    }
+
+    /// Returns the number of times the signal couldn't be sent on a channel
+    /// because the channel wasn't ready.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+/// Return the number of subscribers to the given signal.
+///
+/// Signals aren't supported on this platform, so this is always `0`.
+pub fn subscriber_count(signal: Signal) -> usize {
+    let _ = signal;
+    0
+}
+
+/// Return the number of signals that couldn't be sent on the given channel
+/// because it wasn't ready.
+///
+/// Signals aren't supported on this platform, so nothing is subscribed and
+/// this is always `None`.
+pub fn dropped_count<C: DroppedCount + ?Sized>(chan: &C) -> Option<u64> {
+    chan.dropped_count()
+}
+
+/// One end of a channel that `dropped_count` can look up.
+///
+/// This is implemented for `chan::Sender<Signal>` and
+/// `chan::Receiver<Signal>`.
+pub trait DroppedCount {
+    /// Return the number of signals dropped for this channel, or `None` if
+    /// it isn't subscribed to anything.
+    fn dropped_count(&self) -> Option<u64>;
+}
+
+impl DroppedCount for Sender<Signal> {
+    fn dropped_count(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl DroppedCount for chan::Receiver<Signal> {
+    fn dropped_count(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Return the signals that the given channel is subscribed to.
+///
+/// Signals aren't supported on this platform, so this is always empty.
+pub fn subscriptions(chan: &Sender<Signal>) -> Vec<Signal> {
+    let _ = chan;
+    vec![]
+}
+
+/// Stop sending signals to the given channel until `resume` is called.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn pause(chan: &Sender<Signal>) {
+    let _ = chan;
+}
+
+/// Send the signals held back by `pause` and start sending signals to the
+/// given channel again.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn resume(chan: &Sender<Signal>) {
+    let _ = chan;
+}
+
+/// Return a human readable description of the state of this crate.
+pub fn debug_dump() -> String {
+    "chan-signal: signals are not supported on this platform\n".to_string()
+}
+
+/// Create a new channel that receives the next occurrence of a signal.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_once(signal: Signal) -> chan::Receiver<Signal> {
+    let _ = signal;
+    never()
+}
+
+/// Block the calling thread until one of the given signals arrives, and
+/// return it.
+///
+/// Signals aren't supported on this platform, so this blocks forever.
+pub fn wait_for(signals: &[Signal]) -> Signal {
+    let _ = signals;
+    loop {
+        thread::park();
+    }
+}
+
+/// Block the calling thread until one of the given signals arrives or
+/// `deadline` passes, whichever comes first.
+///
+/// Signals aren't supported on this platform, so this always waits until
+/// `deadline` and returns `None`.
+pub fn notify_until(signals: &[Signal], deadline: Instant) -> Option<Signal> {
+    let _ = signals;
+    let now = Instant::now();
+    if deadline > now {
+        thread::sleep(deadline - now);
+    }
+    None
+}
+
+/// Create a new channel subscribed to the given signals that never holds
+/// more than one unconsumed copy of each signal.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_coalesced(signals: &[Signal]) -> chan::Receiver<Signal> {
+    notify(signals)
+}
+
+/// Create a new channel subscribed to the given signals that receives every
+/// signal that arrived in a burst at once.
+///
+/// Signals aren't supported on this platform, so the channel never receives
+/// anything.
+pub fn notify_batched(signals: &[Signal]) -> chan::Receiver<Vec<Signal>> {
+    let _ = signals;
+    never()
+}
+
+/// Create a new channel that observes the given signals without changing
+/// what happens when they arrive.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn notify_observe(
+    signals: &[Signal],
+) -> io::Result<chan::Receiver<Signal>> {
+    unsupported(signals)?;
+    Ok(never())
+}
+
+/// Create a new stream subscribed to the given signals.
+///
+/// Signals aren't supported on this platform, so the stream never yields
+/// anything.
+///
+/// This is only available when the `async` feature is enabled.
+#[cfg(feature = "async")]
+pub fn notify_stream(signals: &[Signal]) -> SignalStream {
+    let _ = signals;
+    SignalStream { _private: () }
+}
+
+/// A stream of signals created by `notify_stream`.
+#[cfg(feature = "async")]
+pub struct SignalStream {
+    _private: (),
+}
+
+#[cfg(feature = "async")]
+impl futures::Stream for SignalStream {
+    type Item = Signal;
+    type Error = ();
+
+    fn poll(&mut self) -> futures::Poll<Option<Signal>, ()> {
+        // Nothing ever arrives, so there's no task to wake up.
+        Ok(futures::Async::NotReady)
+    }
+}
+
+/// Block the given signals without subscribing to them.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn block<S: Into<SignalSet>>(signals: S) {
+    let _ = signals;
+}
+
+/// Block the given signals without subscribing to them.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn try_block<S: Into<SignalSet>>(signals: S) -> io::Result<()> {
+    unsupported(signals)
+}
+
+/// Block all subscribable signals.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn block_all_subscribable() {}
+
+/// Block all subscribable signals.
+///
+/// Signals aren't supported on this platform, so this always returns an
+/// error.
+pub fn try_block_all_subscribable() -> io::Result<()> {
+    Err(not_supported())
+}
+
+/// Block every signal that can be blocked.
+///
+/// Signals aren't supported on this platform, so this always returns an
+/// error.
+pub fn block_all() -> io::Result<()> {
+    Err(not_supported())
+}
+
+/// Stop the thread that waits for signals and drop every subscription.
+///
+/// Signals aren't supported on this platform, so there is nothing to stop
+/// and this always succeeds.
+pub fn shutdown() -> io::Result<()> {
+    Ok(())
+}
+
+/// Set the name of the thread that waits for signals.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn set_watcher_thread_name(name: &str) {
+    let _ = name;
+}
+
+/// Set the order in which signals that arrive together are delivered.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn set_priority(signal: Signal, priority: u8) {
+    let _ = (signal, priority);
+}
+
+/// Set how many times sending a signal to a full channel is retried.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn set_fan_out(signal: Signal, attempts: u32) {
+    let _ = (signal, attempts);
+}
+
+/// Set how the thread that waits for signals is spawned.
+///
+/// Signals aren't supported on this platform, so no thread is ever spawned
+/// and this always succeeds.
+pub fn set_spawn_hook<F>(hook: F) -> io::Result<()>
+        where F: Fn(Box<dyn FnMut() + Send>) -> io::Result<()>
+                 + Send + Sync + 'static {
+    let _ = hook;
+    Ok(())
+}
+
+/// Exclude signals from ever being blocked by this crate.
+///
+/// Signals aren't supported on this platform, so nothing is ever blocked
+/// and this always succeeds.
+pub fn set_excluded<S: Into<SignalSet>>(signals: S) -> io::Result<()> {
+    let _ = signals;
+    Ok(())
+}
+
+/// Returns true if the thread that waits for signals is running.
+///
+/// Signals aren't supported on this platform, so this is always false.
+pub fn watcher_alive() -> bool {
+    false
+}
+
+/// Return the error that made the thread that waits for signals give up, if
+/// it ever did.
+///
+/// Signals aren't supported on this platform, so this is always `None`.
+pub fn watcher_error() -> Option<io::Error> {
+    None
+}
+
+/// Ignore the given signals.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn ignore(signals: &[Signal]) -> io::Result<()> {
+    unsupported(signals)
+}
+
+/// Set whether system calls interrupted by the given signal are restarted.
+///
+/// Signals aren't supported on this platform, so this always returns
+/// `SignalError::Unsupported`.
+pub fn set_restart(signal: Signal, restart: bool) -> io::Result<()> {
+    let _ = restart;
+    Err(SignalError::Unsupported(signal).into())
+}
+
+/// Hand the given signals back to their default action.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn reset_default(signals: &[Signal]) -> io::Result<()> {
+    unsupported(signals)
+}
+
+/// Hand the given signals back to their default action, even if they still
+/// have subscribers.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn force_reset_default(signals: &[Signal]) -> io::Result<()> {
+    unsupported(signals)
+}
+
+/// Take over a signal from whatever handled it before, so that the previous
+/// handler can be restored later.
+///
+/// Signals aren't supported on this platform, so this always returns
+/// `SignalError::Unsupported`.
+pub fn install_with_previous(signal: Signal) -> io::Result<PreviousHandler> {
+    Err(SignalError::Unsupported(signal).into())
+}
+
+/// The disposition of a signal from before it was handed to this crate with
+/// `install_with_previous`.
+#[derive(Debug)]
+pub struct PreviousHandler {
+    signal: Signal,
+}
+
+impl PreviousHandler {
+    /// Returns the signal whose disposition was recorded.
+    pub fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    /// Reinstall the recorded disposition.
+    ///
+    /// Signals aren't supported on this platform, so this always returns
+    /// `SignalError::Unsupported`.
+    pub fn restore(&self) -> io::Result<()> {
+        Err(SignalError::Unsupported(self.signal).into())
+    }
+}
+
+/// Terminate the process as if it was killed by a signal.
+///
+/// Signals can't be raised on this platform, so this exits with
+/// `signal.exit_code()` instead.
+pub fn reraise_default(signal: Signal) -> ! {
+    process::exit(signal.exit_code())
+}
+
+/// Run a cleanup closure and then terminate the process when any of the
+/// given signals first arrives.
+///
+/// Signals aren't supported on this platform, so the closure is never run.
+pub fn at_signal<F>(signals: &[Signal], cleanup: F)
+        where F: FnOnce() + Send + 'static {
+    let _ = (signals, cleanup);
+}
+
+/// Run a closure when `INT` or `TERM` first arrives, and terminate the
+/// process once it returns or `timeout` passes.
+///
+/// Signals aren't supported on this platform, so the closure is never run.
+pub fn on_terminate_drain<F>(timeout: Duration, drain: F)
+        where F: FnOnce() + Send + 'static {
+    let _ = (timeout, drain);
+}
+
+/// Exit the process with the code returned by a closure when any of the
+/// given signals arrive.
+///
+/// Signals aren't supported on this platform, so the process never exits
+/// because of this.
+pub fn exit_on<F>(signals: &[Signal], code: F) -> ExitGuard
+        where F: Fn(Signal) -> i32 + Send + 'static {
+    let _ = (signals, code);
+    ExitGuard { _private: () }
+}
+
+/// Exit the process with the conventional code when any of the given
+/// signals arrive.
+///
+/// Signals aren't supported on this platform, so the process never exits
+/// because of this.
+pub fn exit_on_default(signals: &[Signal]) -> ExitGuard {
+    exit_on(signals, Signal::exit_code)
+}
+
+/// A guard that cancels `exit_on` when dropped.
+#[must_use]
+#[derive(Debug)]
+pub struct ExitGuard {
+    _private: (),
+}
+
+/// Coordinates a graceful shutdown triggered by a signal.
+///
+/// Signals aren't supported on this platform, so shutdown is never
+/// triggered.
+#[derive(Debug)]
+pub struct GracefulShutdown {
+    token: ShutdownToken,
+}
+
+impl GracefulShutdown {
+    /// Subscribe to the given signals. The first of them to arrive triggers
+    /// shutdown.
+    pub fn new(signals: &[Signal]) -> GracefulShutdown {
+        let _ = signals;
+        GracefulShutdown { token: ShutdownToken { _private: () } }
+    }
+
+    /// Return a new token for observing shutdown.
+    pub fn token(&self) -> ShutdownToken {
+        self.token.clone()
+    }
+}
+
+/// A cheaply cloneable handle for observing a `GracefulShutdown`.
+#[derive(Clone, Debug)]
+pub struct ShutdownToken {
+    _private: (),
+}
+
+impl ShutdownToken {
+    /// Returns true if shutdown has been triggered, which never happens on
+    /// this platform.
+    pub fn is_triggered(&self) -> bool {
+        false
+    }
+
+    /// Block until shutdown is triggered. On this platform, that means
+    /// forever.
+    pub fn wait(&self) -> Signal {
+        loop {
+            thread::park();
+        }
+    }
+
+    /// Return the signal that triggered shutdown, which is always `None` on
+    /// this platform.
+    pub fn triggering_signal(&self) -> Option<Signal> {
+        None
+    }
+}
+
+/// Return the signal mask from before this crate first changed it.
+///
+/// Signals aren't supported on this platform, so this is always `None`.
+pub fn saved_mask() -> Option<BlockedSignals> {
+    None
+}
+
+/// A snapshot of a thread's signal mask. See `saved_mask`.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockedSignals {
+    _private: (),
+}
+
+impl BlockedSignals {
+    /// Returns true if the given signal is blocked in this mask.
+    pub fn contains(&self, signal: Signal) -> bool {
+        let _ = signal;
+        false
+    }
+
+    /// Returns the subscribable signals that are blocked in this mask.
+    pub fn signals(&self) -> SignalSet {
+        SignalSet::new()
+    }
+
+    /// Set the calling thread's signal mask to this mask.
+    ///
+    /// Signals aren't supported on this platform, so this always returns an
+    /// error.
+    pub fn restore(&self) -> io::Result<()> {
+        Err(not_supported())
+    }
+}
+
+/// Return true if the given signal is blocked in the calling thread.
+///
+/// Signals aren't supported on this platform, so this always returns
+/// `SignalError::Unsupported`.
+pub fn is_blocked(signal: Signal) -> io::Result<bool> {
+    Err(SignalError::Unsupported(signal).into())
+}
+
+/// Allow signals to be blocked even if other threads are already running.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn allow_existing_threads() {}
+
+/// Refuse to block signals if other threads are already running.
+///
+/// Signals aren't supported on this platform, so this does nothing.
+pub fn deny_existing_threads() {}
+
+/// Return the number of threads that were running when this crate first
+/// blocked signals.
+///
+/// Signals aren't supported on this platform, so this is always `None`.
+pub fn thread_count_at_init() -> Option<usize> {
+    None
+}
+
+/// Return the signals that are pending for the calling thread or the
+/// process.
+///
+/// Signals aren't supported on this platform, so this always returns an
+/// error.
+pub fn pending() -> io::Result<Vec<Signal>> {
+    Err(not_supported())
+}
+
+/// Accept one of the given signals if it is pending, without waiting.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn check_pending(signals: &[Signal]) -> io::Result<Option<Signal>> {
+    unsupported(signals)?;
+    Ok(None)
+}
+
+/// Send a signal to each of the given processes.
+///
+/// Signals aren't supported on this platform, so every result is
+/// `SignalError::Unsupported`.
+pub fn forward_to(signal: Signal, pids: &[i32]) -> Vec<io::Result<()>> {
+    pids.iter()
+        .map(|_| Err(SignalError::Unsupported(signal).into()))
+        .collect()
+}
+
+/// Call the handler that was installed for a signal before this crate took
+/// it over.
+///
+/// Signals aren't supported on this platform, so this always returns
+/// `SignalError::Unsupported`.
+pub fn call_previous(signal: Signal) -> io::Result<bool> {
+    Err(SignalError::Unsupported(signal).into())
+}
+
+/// Call the handlers that were installed before this crate took over the
+/// given signals whenever they arrive.
+///
+/// Signals aren't supported on this platform, so this returns
+/// `SignalError::Unsupported` unless `signals` is empty.
+pub fn chain_previous(signals: &[Signal]) -> io::Result<()> {
+    unsupported(signals)
+}
+
+/// Send a signal to the current process. (Only used in tests.)
+///
+/// On Windows, `INT` is sent as a `CTRL_C_EVENT` to every process attached
+/// to the console, which is what pressing `^C` does. Every other signal
+/// (e.g., `USR1`), and every signal on other platforms, is unsupported, so
+/// this panics with `SignalError::Unsupported`.
+#[doc(hidden)]
+pub fn kill_this(sig: Signal) {
+    raise(sig).unwrap();
+}
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+extern "system" {
+    fn GenerateConsoleCtrlEvent(ctrl_event: u32, process_group: u32) -> i32;
+}
+
+#[cfg(windows)]
+fn raise(sig: Signal) -> io::Result<()> {
+    const CTRL_C_EVENT: u32 = 0;
+
+    if sig != Signal::INT {
+        return Err(SignalError::Unsupported(sig).into());
+    }
+    // A process group of `0` means every process attached to the console.
+    if unsafe { GenerateConsoleCtrlEvent(CTRL_C_EVENT, 0) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn raise(sig: Signal) -> io::Result<()> {
+    Err(SignalError::Unsupported(sig).into())
+}
+
+/// Make the thread that waits for signals panic. (Only used in tests.)
+#[doc(hidden)]
+pub fn panic_watcher() {}
+
+/// Poison the mutex holding the channel subscriptions. (Only used in
+/// tests.)
+#[doc(hidden)]
+pub fn poison_handlers() {}
+
+/// Ask the thread that waits for signals to refresh them. (Only used in
+/// tests.)
+#[doc(hidden)]
+pub fn request_refresh() {}
+
+/// Queue a signal to the current process along with a value.
+///
+/// Signals aren't supported on this platform, so this always returns
+/// `SignalError::Unsupported`.
+pub fn queue_to_self(sig: Signal, value: usize) -> io::Result<()> {
+    let _ = value;
+    Err(SignalError::Unsupported(sig).into())
+}
+
+/// Returns a channel that never receives anything and is never closed.
+fn never<T: Send + 'static>() -> chan::Receiver<T> {
+    let (s, r) = chan::sync(100);
+    keep(s);
+    r
+}
+
+/// Keep `sender` for the lifetime of the process.
+fn keep<T: Any + Send>(sender: T) {
+    SENDERS.lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(Box::new(sender));
+}
+
+/// Returns an error for the first of `signals`, if any.
+fn unsupported<S: Into<SignalSet>>(signals: S) -> io::Result<()> {
+    match signals.into().iter().next() {
+        None => Ok(()),
+        Some(signal) => Err(SignalError::Unsupported(signal).into()),
+    }
+}
+
+/// The error returned by the functions that aren't about any signal in
+/// particular.
+fn not_supported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "signals are not supported on this platform",
+    )
+}
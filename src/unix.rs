@@ -0,0 +1,4566 @@
+use std::cmp;
+use std::collections::{BTreeMap, HashMap};
+use std::collections::hash_map::Entry;
+use std::fmt;
+use std::hash::Hash;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::fs;
+use std::io;
+use std::iter;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::os::unix::io::AsRawFd;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::os::unix::io::RawFd;
+use std::os::unix::process::ExitStatusExt;
+use std::process;
+use std::ptr;
+use std::sync::atomic::{self, AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Weak, mpsc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use bit_set::BitSet;
+use chan::Sender;
+use libc::{
+    SIGURG,
+
+    SIG_BLOCK,
+    SIG_SETMASK,
+    SIG_UNBLOCK,
+
+    sigset_t,
+    sigaddset, sigdelset, sigismember, sigemptyset, sigfillset, sigpending,
+    pthread_sigmask,
+};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use libc::{signalfd, sigwaitinfo};
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use libc::sigwait;
+use libc::kill;
+use libc::getpid;
+
+use signal::{Sig, Signal, SignalError, SignalSet, realtime_range};
+
+#[cfg(feature = "async")]
+pub use self::stream::{SignalStream, notify_stream};
+
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(all(feature = "kqueue", any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+)))]
+mod kqueue;
+
+lazy_static! {
+    static ref HANDLERS: Mutex<HashMap<Sender<Signal>, Subscription>> =
+        Mutex::new(HashMap::new());
+    // Subscribers that aren't plain channels. These are called on the
+    // watcher thread after all channels have been visited.
+    static ref CALLBACKS: Mutex<Vec<CallbackSub>> =
+        Mutex::new(vec![]);
+    // Channels subscribed with `notify_events_on` and `notify_info_on`.
+    // These are locked after `HANDLERS` and `CALLBACKS`, and never while
+    // holding each other.
+    static ref EVENT_HANDLERS:
+        Mutex<HashMap<Sender<SignalEvent>, Subscription>> =
+            Mutex::new(HashMap::new());
+    static ref INFO_HANDLERS:
+        Mutex<HashMap<Sender<SignalInfo>, Subscription>> =
+            Mutex::new(HashMap::new());
+    // Channels subscribed with `notify_batched`. These are locked after
+    // all of the above.
+    static ref BATCH_HANDLERS:
+        Mutex<HashMap<Sender<Vec<Signal>>, Subscription>> =
+            Mutex::new(HashMap::new());
+    // The drop counters of the channels in `HANDLERS` that were created by
+    // this crate, by the receiver that was handed out. See
+    // `dropped_count`. This is locked after `HANDLERS`.
+    static ref RECEIVERS:
+        Mutex<HashMap<chan::Receiver<Signal>, Weak<AtomicU64>>> =
+            Mutex::new(HashMap::new());
+    // The watcher thread, if it's running. It is kept separate from
+    // `HANDLERS` so that inspecting subscriptions doesn't spawn anything.
+    static ref WATCHER: Mutex<Option<Watcher>> = Mutex::new(None);
+    // The threads waiting in `notify_until`, with the signals each one waits
+    // for. The watcher thread doesn't wait on these signals (except `URG`),
+    // and hands over any of them that it receives anyway. This is locked
+    // after everything else.
+    static ref WAITING: Mutex<Vec<(usize, BitSet)>> = Mutex::new(vec![]);
+    static ref NEXT_CALLBACK_ID: AtomicUsize = AtomicUsize::new(0);
+    // The sequence number of the next signal delivered. See `SignalEvent`.
+    static ref NEXT_SEQ: AtomicU64 = AtomicU64::new(0);
+    // The number of the last signal received by the watcher thread, or `0`.
+    static ref LAST_SIGNAL: AtomicUsize = AtomicUsize::new(0);
+    // Set by `panic_watcher`.
+    static ref PANIC_WATCHER: AtomicBool = AtomicBool::new(false);
+    // The error that last made the watcher thread give up. See
+    // `watcher_error`.
+    static ref WATCHER_ERROR: Mutex<Option<io::Error>> = Mutex::new(None);
+    // A ring of the last `HISTORY_SIZE` signals received. See
+    // `recent_signals`. It has no lock, so reading it never holds up the
+    // watcher thread.
+    static ref HISTORY: Vec<HistorySlot> =
+        (0..HISTORY_SIZE).map(|_| HistorySlot::default()).collect();
+    // The number of signals ever added to `HISTORY`.
+    static ref HISTORY_LEN: AtomicU64 = AtomicU64::new(0);
+    // For each signal number up to `MAX_STATS_SIG`, the number of times it
+    // was received, sent on a channel and dropped. See `stats`.
+    static ref RECEIVED: Vec<AtomicU64> =
+        (0..MAX_STATS_SIG + 1).map(|_| AtomicU64::new(0)).collect();
+    static ref DELIVERED: Vec<AtomicU64> =
+        (0..MAX_STATS_SIG + 1).map(|_| AtomicU64::new(0)).collect();
+    static ref DROPPED: Vec<AtomicU64> =
+        (0..MAX_STATS_SIG + 1).map(|_| AtomicU64::new(0)).collect();
+    static ref WATCHER_NAME: Mutex<String> =
+        Mutex::new("chan-signal".to_string());
+    static ref SPAWN_HOOK: Mutex<Option<SpawnHook>> = Mutex::new(None);
+    // The signals that this crate must never block. See `set_excluded`.
+    static ref EXCLUDED: Mutex<BitSet> = Mutex::new(BitSet::new());
+    // The priority of each signal that has one other than `0`. See
+    // `set_priority`.
+    static ref PRIORITIES: Mutex<HashMap<Sig, u8>> =
+        Mutex::new(HashMap::new());
+    // The number of times a signal is retried for full channels, for each
+    // signal that is retried at all. See `set_fan_out`.
+    static ref FAN_OUT: Mutex<HashMap<Sig, u32>> =
+        Mutex::new(HashMap::new());
+    // The signals whose handlers are installed with `SA_RESTART`. See
+    // `set_restart`.
+    static ref RESTART: Mutex<BitSet> = Mutex::new(BitSet::new());
+    // The signals that have a pass-through handler installed by
+    // `notify_observe`.
+    static ref OBSERVED: Mutex<BitSet> = Mutex::new(BitSet::new());
+    // The signals that have been handed back to the operating system, with
+    // their default action or a restored handler. If the watcher thread
+    // receives one of these, it re-raises it instead of delivering it.
+    static ref DEFAULTED: Mutex<BitSet> = Mutex::new(BitSet::new());
+    // The signals that this crate has blocked at some point. Used to decide
+    // what to unblock when a signal is handed back with `reset_default`.
+    static ref BLOCKED: Mutex<SigSet> = Mutex::new(SigSet::empty());
+    // The realtime signals that this crate has blocked. The watcher thread
+    // waits on these in addition to the subscribable signals.
+    static ref REALTIME: Mutex<SigSet> = Mutex::new(SigSet::empty());
+    // The signal mask of the first thread that had its mask changed by this
+    // crate, from just before it was changed.
+    static ref SAVED_MASK: Mutex<Option<SigSet>> = Mutex::new(None);
+    // The number of threads in the process when `SAVED_MASK` was recorded,
+    // if it could be determined.
+    static ref THREADS_AT_INIT: Mutex<Option<usize>> = Mutex::new(None);
+    // The channels paused with `pause`, with the signals held back for
+    // each, oldest first.
+    static ref PAUSED: Mutex<HashMap<Sender<Signal>, Vec<Signal>>> =
+        Mutex::new(HashMap::new());
+    // Set by `allow_existing_threads` and `deny_existing_threads`.
+    static ref ALLOW_THREADS: AtomicBool = AtomicBool::new(false);
+    static ref DENY_THREADS: AtomicBool = AtomicBool::new(false);
+    // The disposition of each signal at the time this crate first blocked
+    // it. Used by `call_previous`.
+    static ref PREVIOUS: Mutex<HashMap<Sig, libc::sigaction>> =
+        Mutex::new(HashMap::new());
+    // The signals whose previous handler is called by the watcher thread
+    // after every delivery. See `chain_previous`.
+    static ref CHAINED: Mutex<BitSet> = Mutex::new(BitSet::new());
+    // The signals subscribed with `notify_with_escalation`, and those of them
+    // that have been delivered once since the last `reset_escalation`.
+    static ref ESCALATING: Mutex<BitSet> = Mutex::new(BitSet::new());
+    static ref ESCALATION_ARMED: Mutex<BitSet> = Mutex::new(BitSet::new());
+    // The closures registered with `at_signal`, in registration order.
+    static ref CLEANUPS: Mutex<Vec<(BitSet, Cleanup)>> = Mutex::new(vec![]);
+    // The channel subscribed to signals registered with `at_signal`. It is
+    // received on by the thread that runs `CLEANUPS`.
+    static ref CLEANUP_CHAN: Mutex<Option<Sender<Signal>>> = Mutex::new(None);
+}
+
+/// Lock one of the mutexes above, or another one shared with the watcher
+/// thread.
+///
+/// A panic while one of them is held poisons it, but nothing in this crate
+/// leaves the data inside in an unusable state when it panics. So the
+/// poison is ignored rather than making signal handling fail for good.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Create a new channel subscribed to the given signals.
+///
+/// The channel returned is never closed.
+///
+/// This is a convenience function for subscribing to multiple signals at once.
+/// See the documentation of `notify_on` for details.
+///
+/// The channel returned has a small buffer to prevent signals from being
+/// dropped.
+///
+/// This panics if the signals can't be subscribed to, e.g., because one of
+/// them is `KILL` or `STOP`. Use `try_notify` to get an error instead.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::Signal;
+///
+/// let signal = chan_signal::notify(&[Signal::INT, Signal::TERM]);
+///
+/// // Blocks until this process is sent an INT or TERM signal.
+/// // Since the channel is never closed, we can unwrap the received value.
+/// signal.recv().unwrap();
+/// ```
+pub fn notify<S: Into<SignalSet>>(signals: S) -> chan::Receiver<Signal> {
+    try_notify(signals).unwrap()
+}
+
+/// Create a new channel subscribed to the given signals, and return an error
+/// if they can't be subscribed to.
+///
+/// This is like `notify`, except that errors, e.g., from a signal that isn't
+/// supported on this platform or from changing the signal mask, are
+/// returned instead of causing a panic. See `try_notify_on_many`.
+///
+/// Unlike with `try_notify_on_many`, nothing is subscribed if any of the
+/// signals can't be subscribed to, since the channel would be lost.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn try_notify<S>(signals: S) -> io::Result<chan::Receiver<Signal>>
+        where S: Into<SignalSet> {
+    let signals = signals.into();
+    let mut scratch = SigSet::empty();
+    for signal in signals.iter() {
+        check_subscribable(signal)?;
+        scratch.add_signal(signal)?;
+    }
+    let (s, r) = chan::sync(100);
+    try_notify_on_many(&s, signals)?;
+    if let Some(sub) = lock(&HANDLERS).get(&s) {
+        remember_receiver(&r, sub);
+    }
+    // dropping `s` is OK because `try_notify_on_many` acquires one.
+    Ok(r)
+}
+
+/// Create a new channel subscribed to the given signals, and return both of
+/// its ends.
+///
+/// This is like `notify`, except that the sender is returned too, so that it
+/// can be cloned and handed out, e.g., to subsystems that each send their own
+/// shutdown requests on the same channel the signals arrive on.
+///
+/// Note that dropping every sender returned doesn't unsubscribe the channel,
+/// since this crate keeps its own clone of it. The channel is never closed.
+///
+/// This panics if the signals can't be subscribed to. See `notify`.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_channel<S>(
+    signals: S,
+) -> (chan::Sender<Signal>, chan::Receiver<Signal>)
+        where S: Into<SignalSet> {
+    let (s, r) = chan::sync(100);
+    notify_on_many(&s, signals);
+    if let Some(sub) = lock(&HANDLERS).get(&s) {
+        remember_receiver(&r, sub);
+    }
+    (s, r)
+}
+
+/// Receive a signal that has already been sent on a channel, without
+/// blocking.
+///
+/// Returns `None` if no signal is waiting to be received. This is useful for
+/// checking, e.g., whether an `INT` arrived since the last time you looked,
+/// without having to use `chan_select!` directly.
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::Signal;
+///
+/// let signal = chan_signal::notify(&[Signal::INT]);
+/// loop {
+///     if chan_signal::try_recv(&signal).is_some() {
+///         break;
+///     }
+///     // do some work...
+/// }
+/// ```
+pub fn try_recv(chan: &chan::Receiver<Signal>) -> Option<Signal> {
+    let mut signal = None;
+    chan_select! {
+        default => {},
+        chan.recv() -> sig => signal = sig,
+    }
+    signal
+}
+
+/// Create a new channel subscribed to the job control signals that can be
+/// caught: `TSTP`, `TTIN`, `TTOU` and `CONT`.
+///
+/// This is a convenience for, e.g., shells that manage job control
+/// themselves. `STOP` can't be caught, so it is left out. Note that since
+/// the signals are blocked, the process is no longer stopped by `TSTP`,
+/// `TTIN` or `TTOU`.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_job_control() -> chan::Receiver<Signal> {
+    let mut signals = SignalSet::job_control();
+    signals.remove(Signal::STOP);
+    notify(signals)
+}
+
+/// Create a new channel subscribed to the given signals that tags each
+/// signal with the name of a group.
+///
+/// This is useful when several subsystems subscribe to signals separately,
+/// but their signals are received in one place: merging the channels (e.g.,
+/// with `chan_select!`) doesn't say which subscription a signal came from,
+/// while the name does. Every group subscribed to a signal receives it.
+///
+/// The channel returned has a small buffer to prevent signals from being
+/// dropped.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_group(
+    name: &str,
+    signals: &[Signal],
+) -> chan::Receiver<(String, Signal)> {
+    let (s, r) = chan::sync(100);
+    let name = name.to_string();
+    subscribe_callback(signals, Box::new(move |sig| {
+        chan_select! {
+            default => {},
+            s.send((name.clone(), sig)) => {},
+        }
+        true
+    }));
+    r
+}
+
+/// Create a new channel subscribed to a signal that tells apart a quick
+/// second occurrence from a first one.
+///
+/// This supports the common "press ^C again to force quit" pattern: the
+/// first `INT` starts a graceful shutdown, while a second `INT` that arrives
+/// within `window` of the first exits right away. Each occurrence is sent
+/// as `DoublePress::Second` if it arrives within `window` of a preceding
+/// `DoublePress::First`, and as `DoublePress::First` otherwise. So after a
+/// `Second`, or once `window` has passed, the next occurrence is a `First`
+/// again.
+///
+/// The channel returned has a small buffer to prevent signals from being
+/// dropped.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use chan_signal::{DoublePress, Signal};
+///
+/// let int = chan_signal::notify_double(Signal::INT, Duration::from_secs(3));
+/// for press in int.iter() {
+///     match press {
+///         DoublePress::First => println!("shutting down, ^C to force"),
+///         DoublePress::Second => ::std::process::exit(130),
+///     }
+/// }
+/// ```
+pub fn notify_double(
+    signal: Signal,
+    window: Duration,
+) -> chan::Receiver<DoublePress> {
+    let (s, r) = chan::sync(100);
+    // When the last `First` arrived, unless a `Second` has arrived since.
+    let mut first: Option<Instant> = None;
+    subscribe_callback(&[signal], Box::new(move |_| {
+        let now = Instant::now();
+        let press = match first {
+            Some(at) if now.duration_since(at) <= window => {
+                first = None;
+                DoublePress::Second
+            }
+            _ => {
+                first = Some(now);
+                DoublePress::First
+            }
+        };
+        chan_select! {
+            default => {},
+            s.send(press) => {},
+        }
+        true
+    }));
+    r
+}
+
+/// An occurrence of a signal subscribed with `notify_double`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DoublePress {
+    /// The signal arrived for the first time, or long enough after the
+    /// last `First`.
+    First,
+    /// The signal arrived again soon after a `First`.
+    Second,
+}
+
+/// Create a new channel that receives the status of every child process
+/// that exits.
+///
+/// This subscribes to `CHLD`, and each time it arrives, the watcher thread
+/// reaps every child that has exited with `waitpid` and sends one
+/// `ChildEvent` per child. Since several children exiting at about the same
+/// time may result in a single `CHLD`, all of them are reaped, not just
+/// one. Like signals, events are dropped if the channel's buffer is full.
+///
+/// Since this reaps *any* child, there should only be one caller of this
+/// function per process, and nothing else should wait for children, e.g.,
+/// with `std::process::Child::wait`. Otherwise, they compete for each
+/// child's status and only one of them gets it.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use std::process::Command;
+///
+/// let children = chan_signal::notify_children();
+/// let child = Command::new("true").spawn().unwrap();
+/// for event in children.iter() {
+///     if event.pid() == child.id() as i32 {
+///         println!("child exited with {}", event.status());
+///         break;
+///     }
+/// }
+/// ```
+pub fn notify_children() -> chan::Receiver<ChildEvent> {
+    let (s, r) = chan::sync(100);
+    subscribe_callback(&[Signal::CHLD], Box::new(move |_| {
+        loop {
+            let mut status = 0;
+            let pid = unsafe { libc::waitpid(-1, &mut status, libc::WNOHANG) };
+            // `0` means the remaining children are still running, and `-1`
+            // (with `ECHILD`) that there aren't any.
+            if pid <= 0 {
+                break;
+            }
+            let event = ChildEvent {
+                pid,
+                status: process::ExitStatus::from_raw(status),
+            };
+            chan_select! {
+                default => {},
+                s.send(event) => {},
+            }
+        }
+        true
+    }));
+    r
+}
+
+/// A child process that exited. See `notify_children`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChildEvent {
+    pid: i32,
+    status: process::ExitStatus,
+}
+
+impl ChildEvent {
+    /// Returns the process id of the child.
+    pub fn pid(&self) -> i32 {
+        self.pid
+    }
+
+    /// Returns the child's exit status, as returned by `waitpid`.
+    ///
+    /// Use `std::os::unix::process::ExitStatusExt` to find out which signal
+    /// killed the child, if any.
+    pub fn status(&self) -> process::ExitStatus {
+        self.status
+    }
+}
+
+/// Create a new channel that receives the size of the terminal, as
+/// `(rows, columns)`, whenever it is resized.
+///
+/// This subscribes to `WINCH`, and each time it arrives, the new size is
+/// looked up with the `TIOCGWINSZ` ioctl on stdin (or on stdout, if stdin
+/// isn't a terminal). If neither is a terminal, then nothing is sent. Like
+/// signals, sizes are dropped if the channel's buffer is full.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_winch() -> chan::Receiver<(u16, u16)> {
+    let (s, r) = chan::sync(100);
+    subscribe_callback(&[Signal::WINCH], Box::new(move |_| {
+        if let Some(size) = terminal_size() {
+            chan_select! {
+                default => {},
+                s.send(size) => {},
+            }
+        }
+        true
+    }));
+    r
+}
+
+/// Return the size of the terminal attached to stdin or stdout as `(rows,
+/// columns)`.
+fn terminal_size() -> Option<(u16, u16)> {
+    for &fd in &[io::stdin().as_raw_fd(), io::stdout().as_raw_fd()] {
+        let mut ws: libc::winsize = unsafe { mem::zeroed() };
+        if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut ws) } == 0 {
+            return Some((ws.ws_row, ws.ws_col));
+        }
+    }
+    None
+}
+
+/// Create a new `std::sync::mpsc` channel subscribed to the given signals.
+///
+/// This is like `notify`, except that the channel returned comes from the
+/// standard library, for callers that don't otherwise use `chan`. Signals
+/// are forwarded by the watcher thread with a non-blocking send, so if the
+/// channel's buffer is full (i.e., the receiver has fallen behind), then
+/// signals are dropped.
+///
+/// Once the receiver has been dropped, the channel is unsubscribed the next
+/// time one of its signals arrives. The signals stay blocked.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_std(signals: &[Signal]) -> mpsc::Receiver<Signal> {
+    let (s, r) = mpsc::sync_channel(100);
+    subscribe_callback(signals, Box::new(move |sig| {
+        SignalSink::try_send(&s, sig)
+    }));
+    r
+}
+
+/// Something that signals can be sent to without blocking.
+///
+/// This lets signals be delivered to a channel type that this crate doesn't
+/// know about. See `notify_on_sink`. It is implemented for the senders of
+/// `chan` and `std::sync::mpsc`, for weak references to `chan` senders (see
+/// `notify_on_weak`), for the senders of `crossbeam_channel` with the
+/// `crossbeam-channel` feature and, with the `async` feature, for the
+/// unbounded senders of `futures::sync::mpsc`.
+pub trait SignalSink {
+    /// Send a signal to this sink.
+    ///
+    /// This is called on the watcher thread, so it must not block. If the
+    /// signal can't be sent right away (e.g., a buffer is full), it should
+    /// be dropped.
+    ///
+    /// This returns false once the sink has nowhere left to send signals
+    /// (e.g., its receiver was dropped), after which it is unsubscribed.
+    fn try_send(&self, sig: Signal) -> bool;
+}
+
+impl SignalSink for Sender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        // `chan` can't tell us whether the receiver is gone.
+        let chan = self;
+        chan_select! {
+            default => {},
+            chan.send(sig) => {},
+        }
+        true
+    }
+}
+
+impl SignalSink for Weak<Sender<Signal>> {
+    fn try_send(&self, sig: Signal) -> bool {
+        match self.upgrade() {
+            None => false,
+            Some(chan) => chan.try_send(sig),
+        }
+    }
+}
+
+impl SignalSink for mpsc::Sender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        self.send(sig).is_ok()
+    }
+}
+
+impl SignalSink for mpsc::SyncSender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        match mpsc::SyncSender::try_send(self, sig) {
+            Err(mpsc::TrySendError::Disconnected(_)) => false,
+            Ok(()) | Err(mpsc::TrySendError::Full(_)) => true,
+        }
+    }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl SignalSink for crossbeam_channel::Sender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        use crossbeam_channel::TrySendError;
+
+        match crossbeam_channel::Sender::try_send(self, sig) {
+            Err(TrySendError::Disconnected(_)) => false,
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl SignalSink for futures::sync::mpsc::UnboundedSender<Signal> {
+    fn try_send(&self, sig: Signal) -> bool {
+        self.unbounded_send(sig).is_ok()
+    }
+}
+
+/// Subscribe to a signal on a sink.
+///
+/// This is like `notify_on`, except that `signal` is sent to any type that
+/// implements `SignalSink`, e.g., a channel from a crate other than `chan`.
+/// Subscribing the same sink twice means each signal is sent to it twice.
+///
+/// Once the sink reports that it has nowhere left to send signals, it is
+/// unsubscribed the next time one of its signals arrives. The signal stays
+/// blocked.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_on_sink<S>(sink: S, signal: Signal)
+        where S: SignalSink + Send + 'static {
+    subscribe_callback(&[signal], Box::new(move |sig| sink.try_send(sig)));
+}
+
+/// Subscribe to a signal on a channel without keeping the channel alive.
+///
+/// `notify_on` keeps a clone of the sender for the lifetime of the process,
+/// so the channel is never closed. A `chan` sender can't be held weakly by
+/// itself, so instead, the sender given here is shared through an `Arc`,
+/// and this crate only keeps a `Weak` reference to it. Once every `Arc` to
+/// the sender is dropped, the sender is dropped too, which closes the
+/// channel if there are no other senders. The subscription is removed the
+/// next time `signal` arrives. The signal stays blocked.
+///
+/// Signals are sent in the same way as with `notify_on`, so if the
+/// channel's buffer is full, they're dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// extern crate chan;
+/// extern crate chan_signal;
+///
+/// use std::sync::Arc;
+///
+/// use chan_signal::Signal;
+///
+/// # fn main() {
+/// let (s, r) = chan::sync(1);
+/// let s = Arc::new(s);
+/// chan_signal::notify_on_weak(&s, Signal::HUP);
+/// // ... receive signals on `r` ...
+/// drop(s);
+/// // The channel is now closed.
+/// assert_eq!(r.recv(), None);
+/// # }
+/// ```
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_on_weak(chan: &Arc<Sender<Signal>>, signal: Signal) {
+    notify_on_sink(Arc::downgrade(chan), signal);
+}
+
+/// Subscribe to a signal on a channel.
+///
+/// When `signal` is delivered to this process, it will be sent on the channel
+/// given.
+///
+/// Note that a signal is sent using a non-blocking send. Namely, if the
+/// channel's buffer is full (or it has no buffer) and it isn't ready to
+/// rendezvous, then the signal will be dropped.
+///
+/// There is currently no way to unsubscribe. Moreover, the channel given
+/// here will be alive for the lifetime of the process. Therefore, the channel
+/// will never be closed. Use `notify_on_weak` for a channel that can be.
+///
+/// This panics if the signal can't be subscribed to. Use `try_notify_on` to
+/// get an error instead.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_on(chan: &Sender<Signal>, signal: Signal) {
+    notify_on_many(chan, signal);
+}
+
+/// Subscribe to a signal on a channel, and return an error if it can't be
+/// subscribed to.
+///
+/// This is like `notify_on`. See `try_notify_on_many` for the errors that
+/// may be returned.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn try_notify_on(chan: &Sender<Signal>, signal: Signal) -> io::Result<()> {
+    try_notify_on_many(chan, signal)
+}
+
+/// Subscribe to many signals on a channel at once.
+///
+/// This is equivalent to calling `notify_on` for each signal, but the
+/// subscriptions are added and the signals are blocked in one step.
+///
+/// This panics if the signals can't be subscribed to. Use
+/// `try_notify_on_many` to get an error instead.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_on_many<S: Into<SignalSet>>(chan: &Sender<Signal>, signals: S) {
+    try_notify_on_many(chan, signals).unwrap();
+}
+
+/// Subscribe to many signals on a channel at once, and return an error if
+/// they can't be subscribed to.
+///
+/// A signal is rejected if it can't be caught (i.e., `KILL` or `STOP`), is
+/// a fault signal (see `Signal::is_fault`), is excluded (see
+/// `set_excluded`) or isn't supported on this platform (e.g., a realtime
+/// signal where there are none, or an `Other` signal with an invalid
+/// number). The other signals are still subscribed, and the error returned
+/// is the one for the first signal that was rejected. Use `subscriptions`
+/// to see which signals the channel ended up subscribed to.
+///
+/// An error is also returned if the watcher thread can't be started or if
+/// the signal mask can't be changed. In that case, the channel isn't
+/// subscribed to any of the signals.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn try_notify_on_many<S>(chan: &Sender<Signal>, signals: S)
+        -> io::Result<()>
+        where S: Into<SignalSet> {
+    // Set aside the signals that can't be subscribed to, and report the
+    // first of them once the rest are subscribed.
+    let mut scratch = SigSet::empty();
+    let mut valid = SignalSet::new();
+    let mut rejected = None;
+    for signal in signals.into().iter() {
+        let checked = check_subscribable(signal)
+            .and_then(|()| scratch.add_signal(signal));
+        match checked {
+            Ok(()) => { valid.insert(signal); }
+            Err(err) => rejected = rejected.or(Some(err)),
+        }
+    }
+    if valid.is_empty() {
+        return rejected.map_or(Ok(()), Err);
+    }
+    try_start_watcher()?;
+    let before = {
+        let mut subs = lock(&HANDLERS);
+        let sub = subs.entry(chan.clone())
+            .or_insert_with(|| Subscription::new(BitSet::new()));
+        let before = sub.sigs.clone();
+        for signal in valid.iter() {
+            sub.sigs.insert(signal.as_sig() as usize);
+        }
+        before
+    };
+
+    // Make sure that the signals that we want notifications on are blocked.
+    // It does not matter if we block the same signal twice.
+    if let Err(err) = try_block(&valid) {
+        let mut subs = lock(&HANDLERS);
+        if before.is_empty() {
+            subs.remove(chan);
+        } else if let Some(sub) = subs.get_mut(chan) {
+            sub.sigs = before;
+        }
+        return Err(err);
+    }
+    rejected.map_or(Ok(()), Err)
+}
+
+/// Return an error if the given signal can never be subscribed to.
+fn check_subscribable(signal: Signal) -> io::Result<()> {
+    if !signal.is_catchable() {
+        Err(SignalError::Uncatchable(signal).into())
+    } else if signal.is_fault() {
+        Err(SignalError::Fault(signal).into())
+    } else if !platform_supports(signal) {
+        Err(SignalError::Unsupported(signal).into())
+    } else {
+        check_excluded(signal)
+    }
+}
+
+/// Create a new channel subscribed to the given signals that receives
+/// details about the delivery of each signal.
+///
+/// This is like `notify`, except that every signal is accompanied by when it
+/// arrived, a sequence number and the value given to `sigqueue` (e.g., with
+/// `queue_to_self`), if any. See `SignalEvent`.
+///
+/// Values are mostly useful for realtime signals, which are queued, so that
+/// no value is lost when the same signal is sent several times in a row.
+///
+/// The channel returned has a small buffer to prevent signals from being
+/// dropped.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_events<S>(signals: S) -> chan::Receiver<SignalEvent>
+        where S: Into<SignalSet> {
+    let (s, r) = chan::sync(100);
+    notify_events_on(&s, signals);
+    r
+}
+
+/// Subscribe to many signals on a channel that receives the value sent along
+/// with each signal.
+///
+/// This is like `notify_on_many`, except that a `SignalEvent` is sent
+/// instead of a `Signal`. See `notify_events` for details.
+///
+/// This panics if one of the signals can't be subscribed to. See
+/// `try_notify_on_many` for why that may happen.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_events_on<S>(chan: &Sender<SignalEvent>, signals: S)
+        where S: Into<SignalSet> {
+    let signals = signals.into();
+    for signal in signals.iter() {
+        check_subscribable(signal).unwrap();
+    }
+    start_watcher();
+    subscribe(&mut lock(&EVENT_HANDLERS), chan, &signals);
+    block(signals);
+}
+
+/// A signal along with details about its delivery.
+///
+/// See `notify_events`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignalEvent {
+    signal: Signal,
+    received_at: Instant,
+    seq: u64,
+    value: Option<i64>,
+}
+
+impl SignalEvent {
+    /// Returns the signal that arrived.
+    pub fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    /// Returns when the watcher thread received the signal.
+    ///
+    /// Comparing this with the time the event is received from the channel
+    /// shows how long it waited to be read.
+    pub fn received_at(&self) -> Instant {
+        self.received_at
+    }
+
+    /// Returns the sequence number of this delivery.
+    ///
+    /// Every signal delivered by this crate, to any subscriber, gets the
+    /// next number, starting at `0`. So if a channel is subscribed to every
+    /// signal that is sent to the process, a gap between the numbers of two
+    /// consecutive events means that signals in between were dropped.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Returns the value sent with the signal by `sigqueue`.
+    ///
+    /// This is `None` if the signal wasn't sent by `sigqueue` (e.g., it was
+    /// sent by `kill`), or if values aren't supported on this platform.
+    pub fn value(&self) -> Option<i64> {
+        self.value
+    }
+}
+
+/// Create a new channel subscribed to the given signals that receives
+/// information about where each signal came from.
+///
+/// This is like `notify`, except that every signal is accompanied by the
+/// details the operating system provides about it, such as the process that
+/// sent it. See `SignalInfo`.
+///
+/// The channel returned has a small buffer to prevent signals from being
+/// dropped.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::Signal;
+///
+/// let signal = chan_signal::notify_info(&[Signal::TERM]);
+/// let info = signal.recv().unwrap();
+/// if let Some(pid) = info.pid() {
+///     println!("TERM sent by process {}", pid);
+/// }
+/// ```
+pub fn notify_info<S>(signals: S) -> chan::Receiver<SignalInfo>
+        where S: Into<SignalSet> {
+    let (s, r) = chan::sync(100);
+    notify_info_on(&s, signals);
+    r
+}
+
+/// Subscribe to many signals on a channel that receives information about
+/// where each signal came from.
+///
+/// This is like `notify_on_many`, except that a `SignalInfo` is sent
+/// instead of a `Signal`. See `notify_info` for details.
+///
+/// This panics if one of the signals can't be subscribed to. See
+/// `try_notify_on_many` for why that may happen.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_info_on<S>(chan: &Sender<SignalInfo>, signals: S)
+        where S: Into<SignalSet> {
+    let signals = signals.into();
+    for signal in signals.iter() {
+        check_subscribable(signal).unwrap();
+    }
+    start_watcher();
+    subscribe(&mut lock(&INFO_HANDLERS), chan, &signals);
+    block(signals);
+}
+
+/// Information about an arriving signal, taken from its `siginfo_t`.
+///
+/// More fields may be added in the future. On platforms without
+/// `sigwaitinfo`, i.e., other than Linux and Android, only the signal itself
+/// is known.
+///
+/// See `notify_info`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignalInfo {
+    signal: Signal,
+    code: i32,
+    pid: Option<i32>,
+    uid: Option<u32>,
+    value: Option<i64>,
+}
+
+impl SignalInfo {
+    /// Information about `sig` when nothing else is known about it.
+    fn bare(sig: Sig) -> SignalInfo {
+        SignalInfo {
+            signal: Signal::new(sig),
+            code: 0,
+            pid: None,
+            uid: None,
+            value: None,
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn from_siginfo(sig: Sig, info: &libc::siginfo_t) -> SignalInfo {
+        let code = info.si_code;
+        // The sender is only recorded for signals sent by a process, and
+        // for `CHLD`, where it is the child whose status changed.
+        let has_sender =
+            code == libc::SI_USER
+            || code == libc::SI_QUEUE
+            || code == libc::SI_TKILL
+            || (sig == libc::SIGCHLD && code > 0);
+        let (pid, uid) = if has_sender {
+            unsafe { (Some(info.si_pid()), Some(info.si_uid())) }
+        } else {
+            (None, None)
+        };
+        let value = if code == libc::SI_QUEUE {
+            Some(unsafe { info.si_value().sival_ptr as isize as i64 })
+        } else {
+            None
+        };
+        SignalInfo {
+            signal: Signal::new(sig),
+            code,
+            pid,
+            uid,
+            value,
+        }
+    }
+
+    /// Returns the signal that arrived.
+    pub fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    /// Returns the raw `si_code`, which says why the signal was sent.
+    ///
+    /// For example, `SI_USER` (`0`) means it was sent by `kill`, while
+    /// positive values mean it was sent by the kernel. This is always `0`
+    /// on platforms without `sigwaitinfo`.
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    /// Returns true if the signal was sent by the kernel, as opposed to by
+    /// a process (e.g., with `kill`).
+    pub fn is_from_kernel(&self) -> bool {
+        self.code > 0
+    }
+
+    /// Returns the process ID of the sender, if known.
+    ///
+    /// For `CHLD`, this is the child whose status changed.
+    pub fn pid(&self) -> Option<i32> {
+        self.pid
+    }
+
+    /// Returns the real user ID of the sender, if known.
+    pub fn uid(&self) -> Option<u32> {
+        self.uid
+    }
+
+    /// Returns the value sent with the signal by `sigqueue`, if any.
+    pub fn value(&self) -> Option<i64> {
+        self.value
+    }
+}
+
+/// Create a flag that is set when any of the given signals arrives.
+///
+/// This is useful for checking whether, e.g., `INT` has arrived in a hot
+/// loop, where receiving from a channel would be too slow: loading the flag
+/// (even with `Ordering::Relaxed`) is enough. The flag is set by the watcher
+/// thread like any other subscriber. Once set, this crate never clears it.
+///
+/// Once every other clone of the flag has been dropped, it is unsubscribed
+/// the next time one of its signals arrives.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::atomic::Ordering;
+///
+/// use chan_signal::Signal;
+///
+/// let stop = chan_signal::notify_flag(&[Signal::INT, Signal::TERM]);
+/// while !stop.load(Ordering::Relaxed) {
+///     // Do a bit of work.
+/// }
+/// ```
+pub fn notify_flag(signals: &[Signal]) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let watcher_flag = flag.clone();
+    subscribe_callback(signals, Box::new(move |_| {
+        watcher_flag.store(true, Ordering::SeqCst);
+        Arc::strong_count(&watcher_flag) > 1
+    }));
+    flag
+}
+
+/// Create a counter that is incremented every time the given signal
+/// arrives.
+///
+/// This is like `notify_flag`, except that the number of occurrences is
+/// recorded. The counter saturates at `usize::MAX` instead of wrapping. Note
+/// that the operating system may coalesce occurrences of a signal that
+/// arrive while it is pending, so this is a lower bound on the number of
+/// times the signal was sent.
+///
+/// Once every other clone of the counter has been dropped, it is
+/// unsubscribed the next time the signal arrives.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_counter(signal: Signal) -> Arc<AtomicUsize> {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let watcher_counter = counter.clone();
+    subscribe_callback(&[signal], Box::new(move |_| {
+        let mut n = watcher_counter.load(Ordering::SeqCst);
+        while n < usize::MAX {
+            match watcher_counter.compare_exchange(
+                n, n + 1, Ordering::SeqCst, Ordering::SeqCst,
+            ) {
+                Ok(_) => break,
+                Err(current) => n = current,
+            }
+        }
+        Arc::strong_count(&watcher_counter) > 1
+    }));
+    counter
+}
+
+/// Call a closure whenever a signal arrives.
+///
+/// This is an alternative to `notify` for callers that don't want a
+/// channel. The closure is called on the watcher thread, after the signal
+/// has been sent to every subscribed channel. It is not a signal handler,
+/// so it may do anything a normal thread can do. However, no other signal is
+/// delivered (to anything) while it runs, so it should be fast and must not
+/// block. It may subscribe and unsubscribe, but it must not call `shutdown`,
+/// which waits for the watcher thread.
+///
+/// There is no way to unregister the closure. Use `notify_fn` for that.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn on_signal<F>(signal: Signal, f: F)
+        where F: Fn(Signal) + Send + 'static {
+    subscribe_callback(&[signal], Box::new(move |sig| { f(sig); true }));
+}
+
+/// Call a closure whenever one of the given signals arrives, until it is
+/// removed with `remove_callback`.
+///
+/// The closure is called on the watcher thread after `sigwait` returns, not
+/// in a signal handler, so it isn't restricted to async-signal-safe
+/// operations: it may allocate, lock, log and so on. It is called after the
+/// signal has been sent to every subscribed channel, but no other signal is
+/// delivered (to anything) while it runs, so a slow closure delays delivery
+/// of later signals. It may subscribe and unsubscribe, including removing
+/// itself with `remove_callback`, but it must not call `shutdown`, which
+/// waits for the watcher thread.
+///
+/// If the closure panics, then it is removed and the watcher thread keeps
+/// running.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::Signal;
+///
+/// let mut reloads = 0;
+/// let id = chan_signal::notify_fn(&[Signal::HUP], move |_| {
+///     reloads += 1;
+///     println!("reloading configuration (#{})", reloads);
+/// });
+/// // ...
+/// chan_signal::remove_callback(id);
+/// ```
+pub fn notify_fn<F>(signals: &[Signal], mut f: F) -> CallbackId
+        where F: FnMut(Signal) + Send + 'static {
+    CallbackId(subscribe_callback(signals, Box::new(move |sig| {
+        f(sig);
+        true
+    })))
+}
+
+/// Remove a closure registered with `notify_fn`.
+///
+/// If nothing else is subscribed to one of its signals, then that signal is
+/// handed back to its default action (see `reset_default`). If the closure
+/// has already been removed, then this does nothing.
+pub fn remove_callback(id: CallbackId) {
+    unsubscribe_callback(id.0);
+}
+
+/// Identifies a closure registered with `notify_fn`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CallbackId(usize);
+
+/// Create a new channel subscribed to the given signals, where a second
+/// occurrence of a signal gets its default action.
+///
+/// The first occurrence of each signal is delivered like with `notify`. If
+/// the same signal arrives again, its default disposition is restored and
+/// it is re-raised, so that, e.g., a second `INT` kills the process with the
+/// right exit status even if graceful shutdown has hung. Occurrences are
+/// counted per signal. Use `reset_escalation` to start counting from zero,
+/// e.g., after graceful shutdown has been aborted.
+///
+/// Escalation applies to the signal itself, so once a second occurrence
+/// arrives, no subscriber receives it.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_with_escalation(signals: &[Signal]) -> chan::Receiver<Signal> {
+    {
+        let mut escalating = lock(&ESCALATING);
+        for &signal in signals {
+            escalating.insert(signal.as_sig() as usize);
+        }
+    }
+    notify(signals)
+}
+
+/// Forget that the given signals have already been delivered once.
+///
+/// After this, the next occurrence of each signal subscribed with
+/// `notify_with_escalation` is delivered normally again.
+pub fn reset_escalation(signals: &[Signal]) {
+    let mut armed = lock(&ESCALATION_ARMED);
+    for &signal in signals {
+        armed.remove(signal.as_sig() as usize);
+    }
+}
+
+/// Return the signal that was received most recently, if any.
+///
+/// This is updated every time the watcher thread (or the thread started by
+/// `notify_observe`) receives a signal, before it is sent to any subscriber.
+/// So it reflects signals even if they were dropped because a channel's
+/// buffer was full, or if they had no subscribers at all. This makes it
+/// useful for, e.g., checking whether a termination signal has arrived from
+/// anywhere in a program without subscribing to it.
+///
+/// No ordering is guaranteed between this and anything else. In particular,
+/// a signal may have been received from a channel before this returns it.
+pub fn last_signal() -> Option<Signal> {
+    match LAST_SIGNAL.load(Ordering::Relaxed) {
+        0 => None,
+        sig => Some(Signal::new(sig as Sig)),
+    }
+}
+
+/// The number of signals remembered for `recent_signals`.
+const HISTORY_SIZE: usize = 64;
+
+/// Return up to the `n` most recently received signals, along with the time
+/// each one arrived, oldest first.
+///
+/// This is a process-wide record of every signal received by the watcher
+/// thread (or the thread started by `notify_observe`), whether or not it
+/// could be sent to any subscriber. It is useful for post-mortem debugging,
+/// e.g., for finding out why a service restarted. Only the last 64 signals
+/// are remembered.
+///
+/// Reading the history takes no lock, so it never delays delivery. A signal
+/// that is being recorded while the history is read may be left out.
+pub fn recent_signals(n: usize) -> Vec<(Signal, SystemTime)> {
+    let len = HISTORY_LEN.load(Ordering::Acquire);
+    let start = len.saturating_sub(cmp::min(n, HISTORY_SIZE) as u64);
+    (start..len).filter_map(|i| slot(i).read(i)).collect()
+}
+
+/// Add a signal to the history read by `recent_signals`.
+fn record_history(signal: Signal) {
+    let i = HISTORY_LEN.fetch_add(1, Ordering::AcqRel);
+    slot(i).write(i, signal, SystemTime::now());
+}
+
+/// Returns the entry of the history that holds the `i`th signal recorded.
+fn slot(i: u64) -> &'static HistorySlot {
+    &HISTORY[(i % HISTORY_SIZE as u64) as usize]
+}
+
+/// An entry in the history of received signals.
+///
+/// This is a seqlock. `seq` is odd while the entry is written, and
+/// `2 * (i + 1)` once the `i`th signal ever recorded has been written.
+#[derive(Default)]
+struct HistorySlot {
+    seq: AtomicU64,
+    sig: AtomicUsize,
+    secs: AtomicU64,
+    nanos: AtomicUsize,
+}
+
+impl HistorySlot {
+    fn write(&self, i: u64, signal: Signal, at: SystemTime) {
+        let since = at.duration_since(UNIX_EPOCH).unwrap_or_default();
+        self.seq.store(2 * i + 1, Ordering::Relaxed);
+        atomic::fence(Ordering::Release);
+        self.sig.store(signal.as_sig() as usize, Ordering::Relaxed);
+        self.secs.store(since.as_secs(), Ordering::Relaxed);
+        self.nanos.store(since.subsec_nanos() as usize, Ordering::Relaxed);
+        self.seq.store(2 * (i + 1), Ordering::Release);
+    }
+
+    /// Returns the `i`th signal ever recorded, or `None` if this entry is
+    /// being written or no longer holds it.
+    fn read(&self, i: u64) -> Option<(Signal, SystemTime)> {
+        let seq = self.seq.load(Ordering::Acquire);
+        if seq != 2 * (i + 1) {
+            return None;
+        }
+        let sig = self.sig.load(Ordering::Relaxed);
+        let secs = self.secs.load(Ordering::Relaxed);
+        let nanos = self.nanos.load(Ordering::Relaxed);
+        atomic::fence(Ordering::Acquire);
+        if self.seq.load(Ordering::Relaxed) != seq {
+            return None;
+        }
+        let since = Duration::new(secs, nanos as u32);
+        Some((Signal::new(sig as Sig), UNIX_EPOCH + since))
+    }
+}
+
+/// The largest signal number counted by `stats`.
+const MAX_STATS_SIG: usize = 64;
+
+/// Increment the counter for `sig` in `counters`, if it has one.
+fn count_stat(counters: &[AtomicU64], sig: Sig) {
+    if let Some(counter) = counters.get(sig as usize) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Return how many times each signal has been received, delivered and
+/// dropped.
+///
+/// The counters are updated by the watcher thread with atomics, so reading
+/// them never delays the delivery of a signal. Since each counter is read
+/// separately, the snapshot returned may be slightly inconsistent if signals
+/// arrive while it is taken. Signals with a number larger than 64 aren't
+/// counted.
+pub fn stats() -> SignalStats {
+    let mut counts = BTreeMap::new();
+    for sig in 1..MAX_STATS_SIG + 1 {
+        let received = RECEIVED[sig].load(Ordering::Relaxed);
+        if received == 0 {
+            continue;
+        }
+        counts.insert(Signal::new(sig as Sig), SignalCounts {
+            received,
+            delivered: DELIVERED[sig].load(Ordering::Relaxed),
+            dropped: DROPPED[sig].load(Ordering::Relaxed),
+        });
+    }
+    SignalStats(counts)
+}
+
+/// A snapshot of how many times each signal has been received, delivered
+/// and dropped. See `stats`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SignalStats(BTreeMap<Signal, SignalCounts>);
+
+impl SignalStats {
+    /// Returns the counts for the given signal.
+    pub fn get(&self, signal: Signal) -> SignalCounts {
+        self.0.get(&signal).cloned().unwrap_or_default()
+    }
+
+    /// Returns the counts of every signal that has been received at least
+    /// once, in order of signal number.
+    pub fn iter<'a>(&'a self)
+            -> impl Iterator<Item=(Signal, SignalCounts)> + 'a {
+        self.0.iter().map(|(&sig, &counts)| (sig, counts))
+    }
+}
+
+/// How many times a signal has been received, delivered and dropped.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SignalCounts {
+    received: u64,
+    delivered: u64,
+    dropped: u64,
+}
+
+impl SignalCounts {
+    /// Returns the number of times the signal was received by the watcher
+    /// thread (or the thread started by `notify_observe`).
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// Returns the number of times the signal was sent on a channel. Each
+    /// channel that a signal is sent on counts separately, so this may be
+    /// larger than `received`. Signals that were dropped because a channel
+    /// wasn't ready aren't counted, nor are closures called with `on_signal`
+    /// and the like.
+    pub fn delivered(&self) -> u64 {
+        self.delivered
+    }
+
+    /// Returns the number of times the signal couldn't be sent on a channel
+    /// because the channel wasn't ready, i.e., its buffer was full.
+    ///
+    /// Each channel counts separately. Use `dropped_count` to find out which
+    /// channel is falling behind.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+/// Return the number of subscribers to the given signal.
+///
+/// Every subscribed channel counts once, no matter how it was subscribed
+/// (e.g., with `notify_on`, `notify_events_on` or `notify_info_on`), as does
+/// every closure registered with a function like `on_signal` or
+/// `notify_std`. This is useful for detecting subscriptions that were
+/// accidentally made twice or leaked.
+///
+/// Note that there's no way to tell whether a channel's receiver has been
+/// dropped, so a channel that is still subscribed is counted even if nobody
+/// can receive from it anymore.
+pub fn subscriber_count(signal: Signal) -> usize {
+    let sig = signal.as_sig() as usize;
+    let channels = lock(&HANDLERS)
+        .values().filter(|sub| sub.sigs.contains(sig)).count();
+    let callbacks = lock(&CALLBACKS)
+        .iter().filter(|cb| cb.sigs.contains(sig)).count();
+    let events = lock(&EVENT_HANDLERS)
+        .values().filter(|sub| sub.sigs.contains(sig)).count();
+    let infos = lock(&INFO_HANDLERS)
+        .values().filter(|sub| sub.sigs.contains(sig)).count();
+    let batches = lock(&BATCH_HANDLERS)
+        .values().filter(|sub| sub.sigs.contains(sig)).count();
+    channels + callbacks + events + infos + batches
+}
+
+/// Return the number of signals that were dropped because the given channel
+/// wasn't ready to receive them.
+///
+/// Signals are sent with a non-blocking send (see `notify_on`), so if the
+/// receiver falls behind and the channel's buffer fills up, then signals
+/// are silently dropped. This reports how many times that has happened for
+/// a channel. It can be looked up by the sender of a channel subscribed with
+/// `notify_on` (or `notify_on_many`), or by the receiver returned by
+/// `notify`, `try_notify`, `notify_channel` or `notify_observe`, whose
+/// sender isn't at hand.
+///
+/// Returns `None` if the channel isn't subscribed to anything.
+pub fn dropped_count<C: DroppedCount + ?Sized>(chan: &C) -> Option<u64> {
+    chan.dropped_count()
+}
+
+/// One end of a channel that `dropped_count` can look up.
+///
+/// This is implemented for `chan::Sender<Signal>` and
+/// `chan::Receiver<Signal>`.
+pub trait DroppedCount {
+    /// Return the number of signals dropped for this channel, or `None` if
+    /// it isn't subscribed to anything.
+    fn dropped_count(&self) -> Option<u64>;
+}
+
+impl DroppedCount for Sender<Signal> {
+    fn dropped_count(&self) -> Option<u64> {
+        let subs = lock(&HANDLERS);
+        subs.get(self).map(|sub| sub.dropped.load(Ordering::Relaxed))
+    }
+}
+
+impl DroppedCount for chan::Receiver<Signal> {
+    fn dropped_count(&self) -> Option<u64> {
+        let dropped = lock(&RECEIVERS).get(self).and_then(Weak::upgrade);
+        dropped.map(|dropped| dropped.load(Ordering::Relaxed))
+    }
+}
+
+/// Remember the drop counter of a channel created by this crate, so that
+/// `dropped_count` can find it with the receiver handed out.
+fn remember_receiver(chan: &chan::Receiver<Signal>, sub: &Subscription) {
+    let mut receivers = lock(&RECEIVERS);
+    // Forget the channels that have been unsubscribed since.
+    receivers.retain(|_, dropped| dropped.upgrade().is_some());
+    receivers.insert(chan.clone(), Arc::downgrade(&sub.dropped));
+}
+
+/// Return the signals that the given channel is subscribed to, ordered by
+/// their number.
+///
+/// This covers channels subscribed with `notify_on` (or `notify_on_many`).
+/// An empty list is returned if the channel isn't subscribed to anything.
+pub fn subscriptions(chan: &Sender<Signal>) -> Vec<Signal> {
+    match lock(&HANDLERS).get(chan) {
+        None => vec![],
+        Some(sub) => {
+            sub.sigs.iter().map(|sig| Signal::new(sig as Sig)).collect()
+        }
+    }
+}
+
+/// The maximum number of signals held back for a paused channel. Signals
+/// that arrive once this many are held back are dropped.
+const MAX_PAUSED: usize = 1024;
+
+/// Hold back signals for a channel until `resume` is called.
+///
+/// While a channel is paused, the signals it is subscribed to are still
+/// received, but instead of being sent, they're kept in the order they
+/// arrived. This is useful for, e.g., a critical section that must not be
+/// interrupted by handling a `HUP`, but that shouldn't miss one either. At
+/// most 1024 signals are held back for a channel; any more are dropped (and
+/// counted by `dropped_count`).
+///
+/// Pausing a channel that is already paused does nothing.
+pub fn pause(chan: &Sender<Signal>) {
+    lock(&PAUSED).entry(chan.clone()).or_default();
+}
+
+/// Send the signals held back for a paused channel, and stop holding them
+/// back.
+///
+/// The signals are sent in the order they arrived, in the same way that
+/// signals are normally sent (see `notify_on`). So if the channel's buffer
+/// fills up, the rest are dropped.
+///
+/// Resuming a channel that isn't paused does nothing.
+pub fn resume(chan: &Sender<Signal>) {
+    let mut paused = lock(&PAUSED);
+    let held = match paused.remove(chan) {
+        None => return,
+        Some(held) => held,
+    };
+    // Sends don't block, and holding the lock while sending keeps signals
+    // that arrive in the meantime from overtaking the held ones.
+    let chans = [chan.clone()];
+    for signal in held {
+        let dropped =
+            send_each("resumed channel", &chans, signal, signal, 0);
+        count_dropped(&HANDLERS, dropped);
+    }
+}
+
+/// Return a human readable description of every subscription, for
+/// debugging.
+///
+/// This lists each subscribed channel with its signals and the number of
+/// signals dropped for it, each closure registered with a function like
+/// `on_signal`, the signals this crate has blocked or handed back to their
+/// default action, and the signals currently pending. Channel capacities
+/// aren't known, so they aren't listed. The format is meant for people and
+/// may change.
+///
+/// Each registry is locked only long enough to copy what is listed, so this
+/// is cheap enough to call, e.g., every time a `USR2` arrives on a channel.
+/// It may also be called from a closure registered with `on_signal` or the
+/// like, since those are called after the registry has been unlocked.
+pub fn debug_dump() -> String {
+    let mut out = vec![];
+    out.push(format!("watcher: {}", if watcher_alive() {
+        "running"
+    } else {
+        "not running"
+    }));
+    out.extend(dump_subs("channel", &lock(&HANDLERS)));
+    out.extend(dump_subs("event channel", &lock(&EVENT_HANDLERS)));
+    out.extend(dump_subs("info channel", &lock(&INFO_HANDLERS)));
+    out.extend(dump_subs("batch channel", &lock(&BATCH_HANDLERS)));
+    for cb in lock(&CALLBACKS).iter() {
+        out.push(format!(
+            "callback {}: {:?}", cb.id, SignalSet(cb.sigs.clone())));
+    }
+    let blocked = SignalSet::from(lock(&BLOCKED).signals());
+    out.push(format!("blocked: {:?}", blocked));
+    let defaulted = SignalSet(lock(&DEFAULTED).clone());
+    out.push(format!("defaulted: {:?}", defaulted));
+    match SigSet::pending() {
+        Ok(pending) => {
+            let pending = SignalSet::from(pending.signals());
+            out.push(format!("pending: {:?}", pending));
+        }
+        Err(err) => out.push(format!("pending: unknown ({})", err)),
+    }
+    let mut dump = out.join("\n");
+    dump.push('\n');
+    dump
+}
+
+/// Describe each subscription in `subs` for `debug_dump`, in a stable
+/// order.
+fn dump_subs<T>(
+    kind: &str,
+    subs: &HashMap<Sender<T>, Subscription>,
+) -> Vec<String> {
+    let mut lines: Vec<String> = subs.values().map(|sub| {
+        format!(
+            "{} {:?}{}: {} dropped",
+            kind,
+            SignalSet(sub.sigs.clone()),
+            if sub.once { " (once)" } else { "" },
+            sub.dropped.load(Ordering::Relaxed),
+        )
+    }).collect();
+    lines.sort();
+    lines
+}
+
+/// Create a new channel that receives the next occurrence of a signal.
+///
+/// Exactly one value is sent on the channel returned. After that, this
+/// crate drops its copy of the channel's sender, which closes the channel.
+/// If no other subscribers for the signal remain, then it is also handed
+/// back to its default action, just like with `reset_default`. For example,
+/// the first `INT` could start a graceful shutdown while a second `INT`
+/// terminates the process.
+///
+/// If two occurrences arrive nearly simultaneously, only the first is
+/// delivered.
+///
+/// This panics if one of the signals can't be subscribed to. See
+/// `try_notify_on_many` for why that may happen.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_once(signal: Signal) -> chan::Receiver<Signal> {
+    notify_once_many(&[signal])
+}
+
+/// Block the calling thread until one of the given signals arrives, and
+/// return it.
+///
+/// This is a convenience for programs that just want to run until, e.g.,
+/// `INT` or `TERM` arrives. Once a signal arrives, the subscription is
+/// removed just like with `notify_once`: if nothing else is subscribed to
+/// one of the given signals, then it is handed back to its default action.
+/// So a second `INT` terminates the process while it is shutting down.
+///
+/// If `signals` is empty, then this blocks forever.
+///
+/// This panics if one of the signals can't be subscribed to. See
+/// `try_notify_on_many` for why that may happen.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::Signal;
+///
+/// let signal = chan_signal::wait_for(&[Signal::INT, Signal::TERM]);
+/// println!("received {}, shutting down", signal);
+/// ```
+pub fn wait_for(signals: &[Signal]) -> Signal {
+    // The channel is closed once its one signal has been sent, so it's only
+    // `None` if nothing is ever sent, which can't happen.
+    notify_once_many(signals).recv().unwrap()
+}
+
+/// Block the calling thread until one of the given signals arrives or
+/// `deadline` passes, whichever comes first.
+///
+/// This is like `wait_for`, except that `None` is returned if none of the
+/// signals arrived in time. If `deadline` has already passed, this doesn't
+/// block at all and only returns a signal that was already waiting to be
+/// received. Either way, nothing is left subscribed once this returns, just
+/// like with `wait_for`: if nothing else is subscribed to one of the given
+/// signals, then it is handed back to its default action.
+///
+/// On Linux and Android, the calling thread waits for the signals itself
+/// with `sigtimedwait`, and the watcher thread stops waiting on them in the
+/// meantime. If one of them arrives, it is still sent to everything else
+/// that is subscribed to it. If the wait is interrupted (e.g., by a
+/// debugger), it is resumed with whatever time is left until `deadline`,
+/// rather than with the full interval again, so the wait always ends at
+/// `deadline`. Elsewhere, there's no `sigtimedwait`, so the signals are
+/// received by the watcher thread like all others.
+///
+/// This panics if one of the signals can't be subscribed to. See
+/// `try_notify_on_many` for why that may happen.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::{Duration, Instant};
+///
+/// use chan_signal::Signal;
+///
+/// let deadline = Instant::now() + Duration::from_secs(5);
+/// match chan_signal::notify_until(&[Signal::INT], deadline) {
+///     Some(signal) => println!("received {}", signal),
+///     None => println!("timed out"),
+/// }
+/// ```
+pub fn notify_until(signals: &[Signal], deadline: Instant) -> Option<Signal> {
+    for &signal in signals {
+        check_subscribable(signal).unwrap();
+    }
+    wait_until(signals, deadline)
+}
+
+/// Wait for one of the given signals with `sigtimedwait` in the calling
+/// thread. See `notify_until`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn wait_until(signals: &[Signal], deadline: Instant) -> Option<Signal> {
+    let mut set = SigSet::empty();
+    let mut sigs = BitSet::new();
+    for &signal in signals {
+        set.add_signal(signal).unwrap();
+        sigs.insert(signal.as_sig() as usize);
+    }
+    let thread = unsafe { libc::pthread_self() } as usize;
+    lock(&WAITING).push((thread, sigs.clone()));
+    // The watcher is needed to re-raise the signals once they're handed
+    // back to their default action below.
+    start_watcher();
+    refresh_watcher();
+    block(signals);
+    let mut received = set.wait_until(deadline).unwrap();
+    {
+        let mut waiting = lock(&WAITING);
+        if let Some(i) = waiting.iter().position(|w| w.0 == thread) {
+            waiting.remove(i);
+        }
+    }
+    // The watcher may have handed a signal over after the wait ended.
+    if received.is_none() {
+        received = set.poll().unwrap();
+    }
+    refresh_watcher();
+    let subs = lock(&HANDLERS);
+    let callbacks = lock(&CALLBACKS);
+    for sig in sigs.iter() {
+        default_if_unsubscribed(&subs, &callbacks, sig as Sig);
+    }
+    let signal = received?.signal;
+    if is_subscribed(&subs, &callbacks, signal.as_sig() as usize) {
+        drop((subs, callbacks));
+        // Let the watcher send it to everything else that is subscribed.
+        if let Some(ref watcher) = *lock(&WATCHER) {
+            unsafe {
+                libc::pthread_kill(watcher.pthread(), signal.as_sig());
+            }
+        }
+    }
+    Some(signal)
+}
+
+/// Wait for one of the given signals to be sent on a channel by the watcher
+/// thread. See `notify_until`.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn wait_until(signals: &[Signal], deadline: Instant) -> Option<Signal> {
+    let (s, r) = chan::sync(1);
+    subscribe_once(s.clone(), signals);
+    let now = Instant::now();
+    if deadline > now {
+        let timeout = chan::after(deadline - now);
+        chan_select! {
+            r.recv() -> sig => return sig,
+            timeout.recv() => {},
+        }
+    }
+    {
+        let mut subs = lock(&HANDLERS);
+        if let Some(sub) = subs.remove(&s) {
+            let callbacks = lock(&CALLBACKS);
+            for sig in sub.sigs.iter() {
+                default_if_unsubscribed(&subs, &callbacks, sig as Sig);
+            }
+            return None;
+        }
+    }
+    // The watcher thread removed the subscription, so it is about to send
+    // a signal, if it hasn't already. Once it's done, the channel is closed.
+    drop(s);
+    r.recv()
+}
+
+/// Create a new channel that receives the next occurrence of any of the
+/// given signals. See `notify_once`.
+fn notify_once_many(signals: &[Signal]) -> chan::Receiver<Signal> {
+    let (s, r) = chan::sync(1);
+    subscribe_once(s, signals);
+    r
+}
+
+/// Subscribe `s` to the next occurrence of any of the given signals.
+fn subscribe_once(s: Sender<Signal>, signals: &[Signal]) {
+    for &signal in signals {
+        check_subscribable(signal).unwrap();
+    }
+    start_watcher();
+    {
+        let mut sigs = BitSet::new();
+        for &signal in signals {
+            sigs.insert(signal.as_sig() as usize);
+        }
+        let mut sub = Subscription::new(sigs);
+        sub.once = true;
+        lock(&HANDLERS).insert(s, sub);
+    }
+    block(signals);
+}
+
+/// Create a new channel subscribed to the given signals that never holds
+/// more than one unconsumed copy of each signal.
+///
+/// This is like `notify`, except that if a signal arrives while the same
+/// signal is still waiting to be received on the channel, then it is
+/// dropped. This is useful when the receiver only needs to know that a
+/// signal arrived *at least once*, e.g., a child reaper responding to
+/// `CHLD` that calls `waitpid` in a loop anyway.
+///
+/// Note that the operating system already coalesces standard signals that
+/// arrive while the same signal is pending. This adds the same behavior at
+/// the channel level, for signals that have already been received by the
+/// watcher thread but not yet by you.
+///
+/// Delivery order among distinct signals is preserved.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_coalesced(signals: &[Signal]) -> chan::Receiver<Signal> {
+    let mut distinct = BitSet::new();
+    for &signal in signals {
+        distinct.insert(signal.as_sig() as usize);
+    }
+    // The channel never holds more than one copy of each signal, so sending
+    // to it never blocks the watcher.
+    let (s, r) = chan::sync(distinct.len());
+    let unreceived = r.clone();
+    subscribe_callback(signals, Box::new(move |sig| {
+        // Take back the signals that haven't been received yet, and send
+        // them again in the same order, followed by this one unless it's
+        // already among them. Only the watcher sends on this channel, so
+        // nothing can sneak in between.
+        let mut queue = vec![];
+        while let Some(queued) = try_recv(&unreceived) {
+            queue.push(queued);
+        }
+        if !queue.contains(&sig) {
+            queue.push(sig);
+        }
+        for queued in queue {
+            s.send(queued);
+        }
+        true
+    }));
+    r
+}
+
+/// Create a new channel subscribed to the given signals that receives every
+/// signal that arrived in a burst at once.
+///
+/// This is like `notify`, except that after the watcher thread receives a
+/// signal, it also receives every other signal that is already pending, and
+/// sends all of the ones this channel is subscribed to as a single `Vec`.
+/// This is useful for an event loop that wakes up once and handles
+/// everything that arrived since, and it reduces channel traffic during a
+/// storm of signals. A batch is never empty.
+///
+/// Signals in a batch are in the order the watcher thread delivered them
+/// (see `set_priority`). Note that the operating system doesn't record the
+/// order in which distinct signals became pending, and generally hands out
+/// pending signals lowest number first.
+///
+/// Like `notify`, a batch is sent with a non-blocking send, and the channel
+/// returned has a small buffer to prevent batches from being dropped.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn notify_batched(signals: &[Signal]) -> chan::Receiver<Vec<Signal>> {
+    let (s, r) = chan::sync(100);
+    for &signal in signals {
+        check_subscribable(signal).unwrap();
+    }
+    start_watcher();
+    subscribe(&mut lock(&BATCH_HANDLERS), &s, &SignalSet::from(signals));
+    block(signals);
+    r
+}
+
+/// Create a new channel that observes the given signals without changing
+/// what happens when they arrive.
+///
+/// Unlike `notify`, the signals are *not* blocked. Instead, a signal handler
+/// is installed that records each arrival. A helper thread then sends the
+/// signal on the channel (and to any other subscribers) and re-raises it
+/// with its default disposition temporarily restored. So, e.g., an observed
+/// `TSTP` still stops the process and an observed `TERM` still terminates
+/// it. Since nothing is blocked, this does not need to be called before
+/// spawning threads.
+///
+/// Note that when the default action terminates the process, it happens
+/// right after the signal is sent on the channel. There is no guarantee
+/// that the receiver gets a chance to act on it.
+///
+/// If a signal arrives while a previous occurrence is being re-raised, it
+/// gets the default action directly and is not sent on the channel.
+///
+/// An error is returned if any of the given signals is already subscribed
+/// with `notify` (or `notify_on`), since blocked signals never reach the
+/// handler. For the same reason, subscribing to an observed signal later
+/// turns off its pass-through behavior. In that case, the channel returned
+/// here receives the signal like any other subscriber.
+pub fn notify_observe(
+    signals: &[Signal],
+) -> io::Result<chan::Receiver<Signal>> {
+    let mut subs = lock(&HANDLERS);
+    let callbacks = lock(&CALLBACKS);
+    let mut sigs = BitSet::new();
+    for &signal in signals {
+        let sig = signal.as_sig() as usize;
+        if is_subscribed(&subs, &callbacks, sig) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot observe {:?} since it has subscribers",
+                        signal),
+            ));
+        }
+        sigs.insert(sig);
+    }
+    start_observer()?;
+    let mut observed = lock(&OBSERVED);
+    let handler = observe_handler as *const () as libc::sighandler_t;
+    for &signal in signals {
+        set_disposition(signal.as_sig(), handler)?;
+        observed.insert(signal.as_sig() as usize);
+    }
+    let (s, r) = chan::sync(100);
+    let sub = Subscription::new(sigs);
+    remember_receiver(&r, &sub);
+    subs.insert(s, sub);
+    Ok(r)
+}
+
+/// Block all given signals without receiving notifications.
+///
+/// If a signal has also been passed to `notify`/`notify_on` this function
+/// does not have any effect in terms of that signal.
+///
+/// This panics if the signal mask can't be changed. Use `try_block` to get
+/// an error instead.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn block<S: Into<SignalSet>>(signals: S) {
+    try_block(signals).unwrap();
+}
+
+/// Block all given signals without receiving notifications, and return an
+/// error if the signal mask can't be changed.
+///
+/// This is like `block`, except `pthread_sigmask` failing (e.g., because it
+/// is forbidden by a sandbox) is reported instead of causing a panic. If an
+/// error is returned, then the signal mask is unchanged.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn try_block<S: Into<SignalSet>>(signals: S) -> io::Result<()> {
+    let signals = signals.into();
+    let mut block = SigSet::empty();
+    for signal in signals.iter() {
+        check_excluded(signal)?;
+        block.add_signal(signal)?;
+    }
+    save_mask()?;
+    block.thread_block_signals()?;
+    log_debug!("blocked {:?} in the calling thread", signals);
+
+    let mut new_realtime = false;
+    {
+        let mut blocked = lock(&BLOCKED);
+        let mut defaulted = lock(&DEFAULTED);
+        let mut previous = lock(&PREVIOUS);
+        let mut realtime = lock(&REALTIME);
+        for signal in signals.iter() {
+            blocked.add(signal.as_sig())?;
+            defaulted.remove(signal.as_sig() as usize);
+            if let Entry::Vacant(e) = previous.entry(signal.as_sig()) {
+                if let Ok(act) = get_disposition(signal.as_sig()) {
+                    e.insert(act);
+                }
+            }
+            if let Signal::RT(_) = signal {
+                if !realtime.contains(signal.as_sig()) {
+                    realtime.add(signal.as_sig())?;
+                    new_realtime = true;
+                }
+            }
+        }
+    }
+    if new_realtime {
+        refresh_watcher();
+    }
+    Ok(())
+}
+
+/// Block all subscribable signals.
+///
+/// Calling this function effectively restores the default behavior of
+/// version <= 0.2.0 of this library.
+///
+/// The fault signals `SEGV`, `BUS`, `ILL` and `FPE` are not blocked, since
+/// blocking them stops faults from being handled. See `Signal::is_fault`.
+///
+/// This panics if the signal mask can't be changed. Use
+/// `try_block_all_subscribable` to get an error instead.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn block_all_subscribable() {
+    try_block_all_subscribable().unwrap();
+}
+
+/// Block all subscribable signals, and return an error if the signal mask
+/// can't be changed.
+///
+/// This is like `block_all_subscribable`, except errors are reported instead
+/// of causing a panic. If an error is returned, then the signal mask is
+/// unchanged.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn try_block_all_subscribable() -> io::Result<()> {
+    save_mask()?;
+    SigSet::subscribable().thread_block_signals()?;
+    log_debug!("blocked all subscribable signals in the calling thread");
+    *lock(&BLOCKED) = SigSet::subscribable();
+    lock(&DEFAULTED).clear();
+    Ok(())
+}
+
+/// Block every signal that can be blocked in the calling thread.
+///
+/// Unlike `block_all_subscribable`, this blocks every signal the platform
+/// knows about (as given by `sigfillset`), including realtime signals and
+/// signals that can't be subscribed to. This is useful for keeping a process
+/// completely undisturbed, e.g., during initialization. Note that `KILL` and
+/// `STOP` can never be blocked, so they are silently left unblocked by the
+/// operating system.
+///
+/// Beware that blocking signals like `SEGV` means that a fault in the
+/// calling thread kills the process without running any handler.
+///
+/// If an error is returned, then the signal mask is unchanged.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn block_all() -> io::Result<()> {
+    save_mask()?;
+    SigSet::full().without_excluded().thread_block_signals()?;
+    log_debug!("blocked all signals in the calling thread");
+    *lock(&BLOCKED) = SigSet::full().without_excluded();
+    lock(&DEFAULTED).clear();
+    Ok(())
+}
+
+/// Stop the watcher thread and unsubscribe all channels.
+///
+/// This causes the watcher thread to exit and waits for it to do so. All
+/// subscriptions are dropped, which means subscribed channels may be closed
+/// once all other senders are gone. This is useful when this crate is used
+/// from a library that is loaded and unloaded repeatedly.
+///
+/// Signals that were blocked remain blocked, and any signals that arrive
+/// after the watcher thread exits remain pending. A subsequent call to
+/// `notify` (or `notify_on`) starts a fresh watcher thread.
+///
+/// If the watcher thread isn't running, this does nothing.
+pub fn shutdown() -> io::Result<()> {
+    let watcher = match lock(&WATCHER).take() {
+        None => return Ok(()),
+        Some(watcher) => watcher,
+    };
+    watcher.quit.store(true, Ordering::SeqCst);
+    // The watcher blocks every subscribable signal, so directing one at it
+    // is guaranteed to wake up its `sigwait` without affecting other threads.
+    let thread = watcher.pthread();
+    ok_errno((), unsafe { libc::pthread_kill(thread, SIGURG) })?;
+    if let Ok(true) = watcher.done.recv() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other, "chan-signal watcher panicked"));
+    }
+    lock(&HANDLERS).clear();
+    lock(&CALLBACKS).clear();
+    lock(&EVENT_HANDLERS).clear();
+    lock(&INFO_HANDLERS).clear();
+    lock(&BATCH_HANDLERS).clear();
+    lock(&RECEIVERS).clear();
+    lock(&PAUSED).clear();
+    Ok(())
+}
+
+/// Set the name of the thread that waits for signals.
+///
+/// By default, the thread is named `chan-signal`. The name shows up in
+/// debuggers, profilers and panic messages.
+///
+/// The thread is spawned on the first call to `notify` (or `notify_on`), so
+/// this must be called before then. Calling it afterwards has no effect
+/// until the thread is restarted after a `shutdown`.
+pub fn set_watcher_thread_name(name: &str) {
+    *lock(&WATCHER_NAME) = name.to_string();
+}
+
+/// Set the priority of a signal.
+///
+/// When several signals are pending at once, the watcher thread delivers
+/// the ones with a higher priority first. For example, giving `TERM` a
+/// higher priority than `USR1` means that if both arrive in a burst, every
+/// subscriber sees `TERM` first. Signals with the same priority are
+/// delivered in the order the operating system hands them out, which is
+/// generally lowest number first. Every signal has a priority of `0` until
+/// this is called.
+///
+/// Note the limits of this. Priorities only order signals that are pending
+/// at the same time, i.e., that arrive while the watcher thread is busy
+/// (or before it starts). A signal that arrives after a lower priority one
+/// has been accepted is delivered after it. And once a signal has been
+/// sent on a channel, it is up to the receiver when to receive it, e.g.,
+/// `chan_select!` over separate channels picks among ready channels at
+/// random.
+///
+/// Like `ignore`, this may be called at any time.
+pub fn set_priority(signal: Signal, priority: u8) {
+    let mut priorities = lock(&PRIORITIES);
+    if priority == 0 {
+        priorities.remove(&signal.as_sig());
+    } else {
+        priorities.insert(signal.as_sig(), priority);
+    }
+}
+
+/// How long the watcher thread waits before retrying a signal for the
+/// channels that were full. See `set_fan_out`.
+const FAN_OUT_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Retry sending a signal to channels that are full, so that every
+/// subscriber gets it.
+///
+/// Normally, a signal is dropped for a channel whose buffer is full, so a
+/// slow receiver can miss a signal that every other subscriber got. With
+/// this set, the watcher thread instead keeps the signal, waits 10ms and
+/// tries again to send it to each channel that was full, up to `attempts`
+/// times. Only if a channel is still full after that is the signal dropped
+/// for it (and counted by `dropped_count`). Setting `attempts` to `0`
+/// restores the default of never retrying.
+///
+/// This applies to channels subscribed with `notify_on` (or `notify`),
+/// `notify_events_on` and `notify_info_on`. Channels subscribed with
+/// `notify_batched`, callbacks and held back signals sent by `resume` are
+/// never retried.
+///
+/// Note the cost of this. While it retries, the watcher thread can't
+/// deliver any other signal, for up to `10ms * attempts`. And delivery is
+/// still only as reliable as the slowest receiver: a receiver that doesn't
+/// catch up in time misses the signal, just later.
+///
+/// Like `ignore`, this may be called at any time.
+pub fn set_fan_out(signal: Signal, attempts: u32) {
+    let mut fan_out = lock(&FAN_OUT);
+    if attempts == 0 {
+        fan_out.remove(&signal.as_sig());
+    } else {
+        fan_out.insert(signal.as_sig(), attempts);
+    }
+}
+
+/// Returns the number of times `signal` is retried for full channels.
+fn fan_out_attempts(signal: Signal) -> u32 {
+    lock(&FAN_OUT).get(&signal.as_sig()).cloned().unwrap_or(0)
+}
+
+/// Set how the thread that waits for signals is spawned.
+///
+/// By default, it is spawned with `std::thread`. In environments where that
+/// isn't allowed, e.g., a runtime that manages its own OS threads, `hook` is
+/// called instead with the body of the thread. It must arrange for the body
+/// to be called exactly once on an OS thread that is dedicated to it, since
+/// it doesn't return until `shutdown` is called, and return an error if it
+/// can't. The thread's name (see `set_watcher_thread_name`) is up to the
+/// hook. The thread sets its own signal mask, so it doesn't matter which
+/// thread it was spawned from.
+///
+/// The body is a `FnMut` only so that it can be called from a box; calling
+/// it again does nothing.
+///
+/// This must be called before anything else in this crate changes a signal
+/// mask, e.g., before the first call to `notify` or `block`. Otherwise, an
+/// error is returned and the hook isn't used.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::thread;
+///
+/// chan_signal::set_spawn_hook(|mut body| {
+///     thread::Builder::new()
+///         .name("my-signals".to_string())
+///         .spawn(move || body())
+///         .map(|_| ())
+/// }).unwrap();
+/// ```
+pub fn set_spawn_hook<F>(hook: F) -> io::Result<()>
+        where F: Fn(Box<dyn FnMut() + Send>) -> io::Result<()>
+                 + Send + Sync + 'static {
+    if lock(&SAVED_MASK).is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "the spawn hook must be set before any signals are blocked",
+        ));
+    }
+    *lock(&SPAWN_HOOK) = Some(Box::new(hook));
+    Ok(())
+}
+
+/// Set the signals that this crate must leave alone.
+///
+/// Excluded signals are never blocked by this crate, neither in the calling
+/// thread (e.g., by `block_all_subscribable` or `block_all`) nor in the
+/// watcher thread. Subscribing to one, or blocking it explicitly with
+/// `try_block`, returns an error.
+///
+/// This is useful for signals that some other code needs delivered to the
+/// threads of a process. For example, CPU profilers usually rely on `PROF`
+/// or `VTALRM` reaching the thread that is running.
+///
+/// An error is returned if this crate has already blocked signals, since
+/// threads spawned since then may have the excluded signals blocked. `URG`
+/// can't be excluded either: the watcher thread must keep it blocked, since
+/// `shutdown` and other functions direct it at the watcher to wake it up.
+///
+/// # Example
+///
+/// ```
+/// use chan_signal::Signal;
+///
+/// chan_signal::set_excluded(&[Signal::PROF, Signal::VTALRM]).unwrap();
+/// chan_signal::block_all_subscribable();
+/// assert!(!chan_signal::is_blocked(Signal::PROF).unwrap());
+/// ```
+pub fn set_excluded<S: Into<SignalSet>>(signals: S) -> io::Result<()> {
+    if lock(&SAVED_MASK).is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "excluded signals must be set before any signals are blocked",
+        ));
+    }
+    let mut excluded = BitSet::new();
+    for signal in signals.into().iter() {
+        if signal.as_sig() == SIGURG {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "URG is used to wake up the watcher and cannot be excluded",
+            ));
+        }
+        excluded.insert(signal.as_sig() as usize);
+    }
+    *lock(&EXCLUDED) = excluded;
+    Ok(())
+}
+
+/// Return an error if the given signal was excluded with `set_excluded`.
+fn check_excluded(signal: Signal) -> io::Result<()> {
+    if lock(&EXCLUDED).contains(signal.as_sig() as usize) {
+        Err(SignalError::Excluded(signal).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns true if the thread that waits for signals is running.
+///
+/// The thread is spawned on the first call to `notify` (or `notify_on`), so
+/// this returns false before then and after a `shutdown`.
+///
+/// A panic while delivering a signal doesn't stop the thread: the panic is
+/// reported and the thread goes on waiting for signals. If the thread does
+/// exit unexpectedly, a new one is started in its place. Signals that
+/// arrive in between stay pending until the new thread waits for them. The
+/// exception is a thread that gives up because `sigwait` keeps failing; see
+/// `watcher_error`.
+pub fn watcher_alive() -> bool {
+    match *lock(&WATCHER) {
+        None => false,
+        Some(ref watcher) => watcher.alive.load(Ordering::SeqCst),
+    }
+}
+
+/// Return the error that made the thread that waits for signals give up, if
+/// it ever did.
+///
+/// The thread retries `sigwait` when it is interrupted (`EINTR`) or fails
+/// with `EAGAIN`, and it keeps trying after other errors too, but after 100
+/// unexpected errors in a row, it reports the last one here and exits. No
+/// signals are delivered until the next subscription (e.g., a call to
+/// `notify`) starts a new thread, so `watcher_alive` returns false in the
+/// meantime.
+pub fn watcher_error() -> Option<io::Error> {
+    lock(&WATCHER_ERROR).as_ref().map(|err| {
+        let err = match err.raw_os_error() {
+            Some(code) => io::Error::from_raw_os_error(code),
+            None => io::Error::new(err.kind(), err.to_string()),
+        };
+        SignalError::WaitFailed(err).into()
+    })
+}
+
+/// Ignore all given signals for the lifetime of the process.
+///
+/// This sets the disposition of each signal to `SIG_IGN` using `sigaction`,
+/// which means the operating system discards the signal when it is sent.
+/// This is useful for, e.g., ignoring `PIPE` or `HUP` without subscribing
+/// to them.
+///
+/// An error is returned if any of the given signals currently has a
+/// subscribed channel, since an ignored signal would never be delivered to
+/// it. In that case, no dispositions are changed. Similarly, subscribing to
+/// a signal after it has been ignored will not deliver anything. An error is
+/// also returned if the signal cannot be ignored (e.g., `KILL` or `STOP`).
+///
+/// Unlike the other functions in this crate, the disposition of a signal is
+/// process wide, so this may be called at any time.
+pub fn ignore(signals: &[Signal]) -> io::Result<()> {
+    // Hold the lock for the duration so that a concurrent `notify_on` can't
+    // sneak in a subscription for a signal we're about to ignore.
+    let subs = lock(&HANDLERS);
+    let callbacks = lock(&CALLBACKS);
+    for &signal in signals {
+        if is_subscribed(&subs, &callbacks, signal.as_sig() as usize) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot ignore {:?} since it has subscribers", signal),
+            ));
+        }
+    }
+    for &signal in signals {
+        set_disposition(signal.as_sig(), libc::SIG_IGN)?;
+    }
+    Ok(())
+}
+
+/// Set whether system calls interrupted by a signal's handler are
+/// restarted.
+///
+/// This sets or clears the `SA_RESTART` flag of the signal's disposition
+/// with `sigaction`. The flag is kept whenever this crate changes the
+/// disposition later on, e.g., in `notify_observe` or `reset_default`.
+///
+/// Note that the flag only matters while a handler runs in some thread,
+/// which is never the case for a signal that is subscribed to: it is
+/// blocked, and the watcher thread accepts it with `sigwait` instead. So a
+/// blocking system call, like `read`, is never interrupted with `EINTR` by
+/// a subscribed signal, whether or not this is set. It does matter for
+/// signals handled with `notify_observe`, whose handler runs in whichever
+/// thread the signal is delivered to, and for handlers installed by other
+/// code.
+///
+/// An error is returned if the disposition can't be changed (e.g., for
+/// `KILL` or `STOP`).
+///
+/// Like `ignore`, this may be called at any time.
+pub fn set_restart(signal: Signal, restart: bool) -> io::Result<()> {
+    let sig = signal.as_sig();
+    let mut flags = lock(&RESTART);
+    let mut act = get_disposition(sig)?;
+    if restart {
+        act.sa_flags |= libc::SA_RESTART;
+    } else {
+        act.sa_flags &= !libc::SA_RESTART;
+    }
+    if unsafe { libc::sigaction(sig, &act, ptr::null_mut()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if restart {
+        flags.insert(sig as usize);
+    } else {
+        flags.remove(sig as usize);
+    }
+    Ok(())
+}
+
+/// Restore the default disposition of all given signals.
+///
+/// This sets the disposition of each signal to `SIG_DFL` using `sigaction`
+/// and, if the signal was blocked by this crate, unblocks it in the calling
+/// thread. This is useful for handing a signal back to the operating system,
+/// e.g., right before `exec`ing another program (which inherits both the
+/// signal mask and the dispositions of the calling thread).
+///
+/// An error is returned if any of the given signals still has a subscribed
+/// channel, since the watcher thread and the default action would otherwise
+/// race. In that case, nothing is changed. Use `force_reset_default` to
+/// unsubscribe everything from the signals first.
+///
+/// Note that the watcher thread, if it has been started, continues to wait
+/// on every subscribable signal. If it receives a signal that has been
+/// reset, it re-raises it so that the default action still happens. Threads
+/// other than the calling thread keep their signal mask.
+pub fn reset_default(signals: &[Signal]) -> io::Result<()> {
+    let subs = lock(&HANDLERS);
+    let callbacks = lock(&CALLBACKS);
+    for &signal in signals {
+        if is_subscribed(&subs, &callbacks, signal.as_sig() as usize) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot reset {:?} since it has subscribers", signal),
+            ));
+        }
+    }
+    restore_default(signals)
+}
+
+/// Unsubscribe everything from the given signals and restore their default
+/// disposition.
+///
+/// This is like `reset_default`, except that instead of refusing to reset a
+/// signal with subscribers, it removes the signal from every subscribed
+/// channel. Channels that are left without any signals are dropped by this
+/// crate, which means they may be closed once all other senders are gone.
+pub fn force_reset_default(signals: &[Signal]) -> io::Result<()> {
+    let mut subs = lock(&HANDLERS);
+    for &signal in signals {
+        let sig = signal.as_sig() as usize;
+        let chans: Vec<Sender<Signal>> = subs.keys().cloned().collect();
+        for chan in chans {
+            unsubscribe(&mut subs, &chan, sig);
+        }
+    }
+    let mut callbacks = lock(&CALLBACKS);
+    for &signal in signals {
+        let sig = signal.as_sig() as usize;
+        for cb in callbacks.iter_mut() {
+            cb.sigs.remove(sig);
+        }
+    }
+    callbacks.retain(|cb| !cb.sigs.is_empty());
+    remove_signals(&mut lock(&EVENT_HANDLERS), signals);
+    remove_signals(&mut lock(&INFO_HANDLERS), signals);
+    remove_signals(&mut lock(&BATCH_HANDLERS), signals);
+    restore_default(signals)
+}
+
+fn restore_default(signals: &[Signal]) -> io::Result<()> {
+    hand_back(signals, |sig| set_disposition(sig, libc::SIG_DFL))
+}
+
+/// Hand the given signals back to the operating system, after installing
+/// their new disposition with `set`.
+///
+/// If the watcher thread receives one of these signals anyway, it re-raises
+/// it in its own thread so that the new disposition takes effect.
+fn hand_back<F>(signals: &[Signal], set: F) -> io::Result<()>
+        where F: Fn(Sig) -> io::Result<()> {
+    let mut blocked = lock(&BLOCKED);
+    let mut unblock = SigSet::empty();
+    for &signal in signals {
+        set(signal.as_sig())?;
+        lock(&DEFAULTED).insert(signal.as_sig() as usize);
+        if blocked.contains(signal.as_sig()) {
+            unblock.add(signal.as_sig())?;
+        }
+    }
+    unblock.thread_unblock_signals()?;
+    for &signal in signals {
+        blocked.remove(signal.as_sig())?;
+    }
+    Ok(())
+}
+
+/// Record the current disposition of a signal, and then block it.
+///
+/// This is for cooperating with other code that installs its own signal
+/// handler, e.g., a C library. The signal is handed to this crate like with
+/// `try_block`, so it can be subscribed to as usual. Later, the handler that
+/// was installed beforehand can be given back with `PreviousHandler::restore`.
+///
+/// Note that, like the rest of this crate, only the calling thread's signal
+/// mask is changed, and threads spawned afterwards inherit it. Restoring the
+/// handler only unblocks the signal in the thread that calls `restore`, so
+/// any thread that was spawned in between keeps the signal blocked.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn install_with_previous(signal: Signal) -> io::Result<PreviousHandler> {
+    let act = get_disposition(signal.as_sig())?;
+    try_block(signal)?;
+    Ok(PreviousHandler { signal, act })
+}
+
+/// The disposition of a signal from before it was handed to this crate with
+/// `install_with_previous`.
+pub struct PreviousHandler {
+    signal: Signal,
+    act: libc::sigaction,
+}
+
+impl PreviousHandler {
+    /// Returns the signal whose disposition was recorded.
+    pub fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    /// Reinstall the recorded disposition and unblock the signal in the
+    /// calling thread.
+    ///
+    /// This is like `reset_default`, except that the recorded disposition is
+    /// installed instead of `SIG_DFL`. Similarly, an error is returned if
+    /// the signal still has subscribers, in which case nothing is changed
+    /// and this may be called again once they're gone.
+    pub fn restore(&self) -> io::Result<()> {
+        let subs = lock(&HANDLERS);
+        let callbacks = lock(&CALLBACKS);
+        if is_subscribed(&subs, &callbacks, self.signal.as_sig() as usize) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot restore {:?} since it has subscribers",
+                        self.signal),
+            ));
+        }
+        let act = self.act;
+        hand_back(&[self.signal], |sig| {
+            let ecode = unsafe { libc::sigaction(sig, &act, ptr::null_mut()) };
+            if ecode != 0 { Err(io::Error::last_os_error()) } else { Ok(()) }
+        })
+    }
+}
+
+impl fmt::Debug for PreviousHandler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PreviousHandler")
+            .field("signal", &self.signal)
+            .field("handler", &self.act.sa_sigaction)
+            .finish()
+    }
+}
+
+/// Terminate the process by re-raising a signal with its default action.
+///
+/// This is useful after cleaning up in response to a signal like `INT` or
+/// `TERM`. Unlike calling `std::process::exit`, the parent process (e.g., a
+/// shell) sees that this process was killed by the signal, which is what job
+/// control, `timeout(1)` and friends expect.
+///
+/// Specifically, this restores the default disposition of the signal,
+/// unblocks it in the calling thread and then sends it to the calling
+/// thread. Nothing is flushed before the process dies.
+///
+/// This never returns. If the signal does not terminate the process (e.g.,
+/// because its default action is to ignore it), then the process is
+/// aborted.
+pub fn reraise_default(signal: Signal) -> ! {
+    let sig = signal.as_sig();
+    let mut set = SigSet::empty();
+    // There isn't much we can do about errors here. If any of these fail,
+    // then we'll just fall through to the abort below.
+    let _ = set_disposition(sig, libc::SIG_DFL);
+    let _ = set.add(sig);
+    let _ = set.thread_unblock_signals();
+    // The signal is sent to this thread specifically instead of the process.
+    // Otherwise, the watcher thread (which is always waiting on every
+    // subscribable signal) might consume it.
+    unsafe {
+        libc::pthread_kill(libc::pthread_self(), sig);
+        libc::abort();
+    }
+}
+
+/// Run a cleanup closure and then terminate the process when any of the
+/// given signals first arrives.
+///
+/// When one of the signals is received, every closure registered for it is
+/// run in registration order on a thread managed by this crate. Since this
+/// happens outside of a signal handler, the closures may do arbitrary work,
+/// e.g., remove a PID file or flush a log. If a closure panics, the rest
+/// still run. Afterwards, the process is terminated with `reraise_default`,
+/// so that its exit status reflects the signal.
+///
+/// Cleanup happens at most once. Signals that arrive while the closures are
+/// running are sent to subscribed channels as usual, but otherwise have no
+/// effect.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::Signal;
+///
+/// chan_signal::at_signal(&[Signal::INT, Signal::TERM], || {
+///     let _ = std::fs::remove_file("/var/run/myapp.pid");
+/// });
+/// ```
+pub fn at_signal<F>(signals: &[Signal], cleanup: F)
+        where F: FnOnce() + Send + 'static {
+    let mut sigs = BitSet::new();
+    for &signal in signals {
+        sigs.insert(signal.as_sig() as usize);
+    }
+    let mut cleanup = Some(cleanup);
+    lock(&CLEANUPS).push((sigs, Box::new(move || {
+        if let Some(cleanup) = cleanup.take() {
+            cleanup();
+        }
+    })));
+    let mut chan = lock(&CLEANUP_CHAN);
+    if let Some(ref s) = *chan {
+        for &signal in signals {
+            notify_on(s, signal);
+        }
+        return;
+    }
+    let (s, r) = chan::sync(1);
+    // Subscribe before spawning, so that the thread inherits the signal mask.
+    for &signal in signals {
+        notify_on(&s, signal);
+    }
+    thread::Builder::new()
+        .name("chan-signal-cleanup".to_string())
+        .spawn(move || {
+            // Signals passed to later calls must not be delivered here.
+            let _ = SigSet::subscribable().thread_block_signals();
+            run_cleanups(r.recv().unwrap())
+        })
+        .expect("failed to spawn chan-signal cleanup thread");
+    *chan = Some(s);
+}
+
+/// A closure registered with `at_signal`. It does nothing after the first
+/// call.
+type Cleanup = Box<dyn FnMut() + Send>;
+
+/// Run the closures registered for `signal` with `at_signal`, and then
+/// terminate the process.
+fn run_cleanups(signal: Signal) -> ! {
+    let cleanups = mem::take(&mut *lock(&CLEANUPS));
+    for (sigs, cleanup) in cleanups {
+        if sigs.contains(signal.as_sig() as usize) {
+            let _ = panic::catch_unwind(AssertUnwindSafe(cleanup));
+        }
+    }
+    reraise_default(signal)
+}
+
+/// Drain in-flight work and then terminate the process when `INT` or `TERM`
+/// first arrives.
+///
+/// When one of the signals is received, `drain` is run on a thread managed
+/// by this crate. It should stop accepting new work and wait for work that
+/// is in progress to finish. Once it returns, the process is terminated with
+/// `reraise_default`, so that its exit status reflects the signal. If `drain`
+/// is still running after `timeout`, the process is terminated anyway. The
+/// same happens right away if `drain` panics.
+///
+/// Only the first signal starts a drain. Later signals are sent to
+/// subscribed channels as usual, but otherwise have no effect.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// chan_signal::on_terminate_drain(Duration::from_secs(10), || {
+///     // Stop accepting connections and wait for open ones to close.
+/// });
+/// ```
+pub fn on_terminate_drain<F>(timeout: Duration, drain: F)
+        where F: FnOnce() + Send + 'static {
+    let mut drain = Some(drain);
+    subscribe_callback(&[Signal::INT, Signal::TERM], Box::new(move |sig| {
+        if let Some(drain) = drain.take() {
+            start_drain(sig, timeout, drain);
+        }
+        true
+    }));
+}
+
+/// Run `drain` on a new thread, and terminate the process with `signal` once
+/// it returns or `timeout` has passed, whichever comes first.
+///
+/// This is called on the watcher thread, so the threads spawned here
+/// inherit its mask, in which every subscribable signal is blocked.
+fn start_drain<F>(signal: Signal, timeout: Duration, drain: F)
+        where F: FnOnce() + Send + 'static {
+    let (done_send, done) = mpsc::channel();
+    thread::Builder::new()
+        .name("chan-signal-drain".to_string())
+        .spawn(move || {
+            let _ = panic::catch_unwind(AssertUnwindSafe(drain));
+            let _ = done_send.send(());
+        })
+        .expect("failed to spawn chan-signal drain thread");
+    thread::Builder::new()
+        .name("chan-signal-drain-timeout".to_string())
+        .spawn(move || {
+            let _ = done.recv_timeout(timeout);
+            reraise_default(signal)
+        })
+        .expect("failed to spawn chan-signal drain thread");
+}
+
+/// Exit the process when any of the given signals arrive.
+///
+/// When one of the signals is received, `code` is called with it and the
+/// process exits with the code returned, using `std::process::exit`. This
+/// happens on the watcher thread, after the signal has been sent to every
+/// subscribed channel, so this may be combined with other subscriptions to
+/// the same signals. No destructors are run.
+///
+/// The guard returned cancels this when it is dropped. If the signals
+/// aren't subscribed to by anything else at that point, then they are
+/// handed back to their default action, just like with `reset_default`.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::Signal;
+///
+/// let _guard = chan_signal::exit_on(&[Signal::INT, Signal::TERM], |_| 1);
+/// ```
+pub fn exit_on<F>(signals: &[Signal], code: F) -> ExitGuard
+        where F: Fn(Signal) -> i32 + Send + 'static {
+    let id = subscribe_callback(signals, Box::new(move |sig| {
+        ::std::process::exit(code(sig))
+    }));
+    ExitGuard { id }
+}
+
+/// Exit the process with the conventional code when any of the given
+/// signals arrive.
+///
+/// This is like `exit_on`, except the exit code is always `128` plus the
+/// signal number, which is what shells report for a process killed by that
+/// signal. E.g., `INT` exits with `130`. (Use `reraise_default` to actually
+/// die by the signal.)
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.**
+pub fn exit_on_default(signals: &[Signal]) -> ExitGuard {
+    exit_on(signals, Signal::exit_code)
+}
+
+/// A guard that cancels `exit_on` when dropped.
+#[must_use]
+#[derive(Debug)]
+pub struct ExitGuard {
+    id: usize,
+}
+
+impl Drop for ExitGuard {
+    fn drop(&mut self) {
+        unsubscribe_callback(self.id);
+    }
+}
+
+/// Coordinates a graceful shutdown triggered by a signal.
+///
+/// A channel returned by `notify` delivers each signal to only one receiver.
+/// A `GracefulShutdown` instead hands out any number of `ShutdownToken`s,
+/// all of which observe the first of the given signals to arrive. This is
+/// useful for telling many worker threads to stop.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::thread;
+/// use chan_signal::{GracefulShutdown, Signal};
+///
+/// let shutdown = GracefulShutdown::new(&[Signal::INT, Signal::TERM]);
+/// let workers: Vec<_> = (0..4).map(|_| {
+///     let token = shutdown.token();
+///     thread::spawn(move || {
+///         while !token.is_triggered() {
+///             // Do some work.
+///         }
+///     })
+/// }).collect();
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct GracefulShutdown {
+    token: ShutdownToken,
+}
+
+impl GracefulShutdown {
+    /// Subscribe to the given signals. The first of them to arrive triggers
+    /// shutdown.
+    ///
+    /// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+    /// PROCESS.**
+    pub fn new(signals: &[Signal]) -> GracefulShutdown {
+        let token = ShutdownToken {
+            state: Arc::new(ShutdownState {
+                signal: Mutex::new(None),
+                cond: Condvar::new(),
+            }),
+        };
+        let state = token.state.clone();
+        subscribe_callback(signals, Box::new(move |sig| {
+            state.trigger(sig);
+            true
+        }));
+        GracefulShutdown { token }
+    }
+
+    /// Return a new token for observing shutdown.
+    ///
+    /// A token created after shutdown has been triggered observes it too.
+    pub fn token(&self) -> ShutdownToken {
+        self.token.clone()
+    }
+}
+
+/// A cheaply cloneable handle for observing a `GracefulShutdown`.
+#[derive(Clone, Debug)]
+pub struct ShutdownToken {
+    state: Arc<ShutdownState>,
+}
+
+impl ShutdownToken {
+    /// Returns true if shutdown has been triggered.
+    pub fn is_triggered(&self) -> bool {
+        self.triggering_signal().is_some()
+    }
+
+    /// Block until shutdown is triggered, and return the signal that
+    /// triggered it.
+    pub fn wait(&self) -> Signal {
+        let mut signal = lock(&self.state.signal);
+        loop {
+            if let Some(sig) = *signal {
+                return sig;
+            }
+            signal = self.state.cond.wait(signal)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+    }
+
+    /// Return the signal that triggered shutdown, if it has been triggered.
+    pub fn triggering_signal(&self) -> Option<Signal> {
+        *lock(&self.state.signal)
+    }
+}
+
+#[derive(Debug)]
+struct ShutdownState {
+    /// The first signal that arrived.
+    signal: Mutex<Option<Signal>>,
+    cond: Condvar,
+}
+
+impl ShutdownState {
+    fn trigger(&self, sig: Signal) {
+        let mut signal = lock(&self.signal);
+        if signal.is_none() {
+            *signal = Some(sig);
+            self.cond.notify_all();
+        }
+    }
+}
+
+/// Return the signal mask from before this crate first changed it.
+///
+/// This is the mask of the thread that first called a function in this
+/// crate that blocks signals, e.g., `notify` or `block`. Typically, that's
+/// the main thread. The mask is captured right before it was first changed
+/// and it includes every blocked signal, not just the subscribable ones.
+/// This is useful for library code that wants to leave the signal mask of
+/// its caller exactly as it found it.
+///
+/// Returns `None` if this crate hasn't changed any signal mask.
+pub fn saved_mask() -> Option<BlockedSignals> {
+    lock(&SAVED_MASK).map(BlockedSignals)
+}
+
+/// A snapshot of a thread's signal mask. See `saved_mask`.
+#[derive(Clone, Copy)]
+pub struct BlockedSignals(SigSet);
+
+impl BlockedSignals {
+    /// Returns true if the given signal is blocked in this mask.
+    pub fn contains(&self, signal: Signal) -> bool {
+        self.0.contains(signal.as_sig())
+    }
+
+    /// Returns the subscribable signals that are blocked in this mask.
+    pub fn signals(&self) -> SignalSet {
+        self.0.signals().into()
+    }
+
+    /// Set the calling thread's signal mask to this mask.
+    ///
+    /// Note that restoring a mask in which a subscribed signal is not
+    /// blocked means that the calling thread may receive it directly.
+    pub fn restore(&self) -> io::Result<()> {
+        self.0.thread_set_signal_mask()
+    }
+}
+
+impl fmt::Debug for BlockedSignals {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("BlockedSignals").field(&self.signals()).finish()
+    }
+}
+
+/// Return true if the given signal is blocked in the calling thread.
+///
+/// Signal masks are per thread, and a thread inherits the mask of the thread
+/// that spawned it. So this is useful for finding out why a signal isn't
+/// delivered to a channel: if it isn't blocked in some thread, e.g., one
+/// that was spawned before `notify` was called, then that thread may receive
+/// the signal instead of the watcher thread.
+///
+/// An error is returned if the signal isn't supported on this platform.
+pub fn is_blocked(signal: Signal) -> io::Result<bool> {
+    let mask = SigSet::current()?;
+    match unsafe { sigismember(&mask.0, signal.as_sig()) } {
+        -1 => Err(io::Error::last_os_error()),
+        n => Ok(n == 1),
+    }
+}
+
+/// Record the calling thread's signal mask in `SAVED_MASK`, unless a mask
+/// has already been recorded.
+///
+/// If other threads are running, a warning is printed, or an error is
+/// returned if `deny_existing_threads` was called. In the latter case,
+/// nothing is recorded, so the next call checks again.
+fn save_mask() -> io::Result<()> {
+    let mut saved = lock(&SAVED_MASK);
+    if saved.is_none() {
+        let count = count_threads();
+        if let Some(n) = count {
+            if n > 1 && DENY_THREADS.load(Ordering::SeqCst) {
+                return Err(SignalError::ThreadsAlreadySpawned(n).into());
+            }
+            if n > 1 && !ALLOW_THREADS.load(Ordering::SeqCst) {
+                warn_existing_threads(n);
+            }
+        }
+        *saved = SigSet::current().ok();
+        *lock(&THREADS_AT_INIT) = count;
+    }
+    Ok(())
+}
+
+/// Warn that `n` threads were running when signals were first blocked.
+///
+/// Without the `log` feature, this is printed to stderr instead, since the
+/// signals those threads receive are silently lost. `save_mask` only counts
+/// threads once, so this is printed at most once.
+#[cfg(feature = "log")]
+fn warn_existing_threads(n: usize) {
+    log_warn!(
+        "signals were first blocked while {} threads were running; \
+         threads other than the calling thread may still receive them",
+        n);
+}
+
+#[cfg(not(feature = "log"))]
+fn warn_existing_threads(n: usize) {
+    eprintln!(
+        "chan-signal: signals were first blocked while {} threads were \
+         running; threads other than the calling thread may still receive \
+         them (call allow_existing_threads to silence this)",
+        n);
+}
+
+/// Don't warn if other threads are running when this crate first blocks
+/// signals.
+///
+/// This is for programs that know the threads spawned before calling
+/// `notify` can't receive the subscribed signals, e.g., because they block
+/// all signals themselves. The number of threads is still recorded, see
+/// `thread_count_at_init`.
+///
+/// This has no effect if it's called after signals have been blocked.
+pub fn allow_existing_threads() {
+    ALLOW_THREADS.store(true, Ordering::SeqCst);
+}
+
+/// Refuse to block signals if other threads are running when this crate
+/// first blocks signals.
+///
+/// Instead of printing a warning, the first function that blocks signals
+/// (e.g., `try_notify` or `try_block`) returns an error that wraps
+/// `SignalError::ThreadsAlreadySpawned`, and leaves the signal mask alone.
+/// Functions that don't return a `Result`, like `notify`, panic instead.
+/// This turns a subtle misuse of this crate into an immediate failure,
+/// e.g., in tests.
+///
+/// This only works where the number of threads can be determined (see
+/// `thread_count_at_init`), and has no effect if it's called after signals
+/// have been blocked.
+pub fn deny_existing_threads() {
+    DENY_THREADS.store(true, Ordering::SeqCst);
+}
+
+/// Return the number of threads that were running when this crate first
+/// blocked signals.
+///
+/// Blocking a signal only affects the calling thread and the threads it
+/// spawns afterwards (which inherit its signal mask). There is no way to
+/// block a signal in threads that already exist, so they may still receive
+/// signals that are subscribed to, which means those signals are never
+/// delivered to any channel. This crate detects this by counting the threads
+/// in the process the first time it blocks signals, e.g., in `notify`. If
+/// there's more than one, a warning is logged with the `log` feature, or
+/// printed to stderr without it, unless `allow_existing_threads` was
+/// called. Use `deny_existing_threads` to get an error instead.
+///
+/// Returns `None` if this crate hasn't blocked any signals yet, or if the
+/// number of threads can't be determined on this platform (it is only
+/// supported on Linux and Android).
+pub fn thread_count_at_init() -> Option<usize> {
+    *lock(&THREADS_AT_INIT)
+}
+
+/// Count the threads in this process.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn count_threads() -> Option<usize> {
+    fs::read_dir("/proc/self/task").ok().map(|dir| dir.count())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn count_threads() -> Option<usize> {
+    None
+}
+
+/// Return the signals that are currently pending.
+///
+/// A signal is pending if it has been sent to this process (or the calling
+/// thread) but hasn't been delivered yet because it is blocked. This is
+/// useful for detecting, e.g., a `TERM` that arrived before `notify` was
+/// called but after the signal was blocked. Only subscribable signals are
+/// returned.
+///
+/// Note that once the watcher thread has been started, it accepts every
+/// signal that this crate has blocked as soon as it arrives. So signals
+/// subscribed with `notify` rarely show up here.
+pub fn pending() -> io::Result<Vec<Signal>> {
+    Ok(SigSet::pending()?.signals())
+}
+
+/// Accept one of the given signals if it is pending, without blocking.
+///
+/// If more than one of them is pending, only one is accepted, and the rest
+/// stay pending. Returns `None` if none of them is pending. An error is
+/// returned if one of the signals isn't supported on this platform.
+///
+/// Like `pending`, this only finds signals that are blocked. Since the
+/// watcher thread accepts every signal that this crate has blocked as soon
+/// as it arrives, this is mostly useful for signals that are blocked before
+/// the watcher thread is started, or for signals excluded with
+/// `set_excluded` that you block yourself.
+pub fn check_pending(signals: &[Signal]) -> io::Result<Option<Signal>> {
+    let mut set = SigSet::empty();
+    for &signal in signals {
+        set.add_signal(signal)?;
+    }
+    Ok(set.poll()?.map(|info| info.signal))
+}
+
+/// Send a signal to each of the given processes.
+///
+/// This is useful for relaying signals received on a channel to child
+/// processes, e.g., in a supervisor that passes `TERM` on to its children.
+/// Every process is sent the signal, even if sending it to an earlier one
+/// failed, and the result of each send is returned in the same order as
+/// `pids`.
+///
+/// Each pid is passed to `kill` as is, so the usual special values apply:
+/// `0` sends the signal to every process in the caller's process group,
+/// `-1` sends it to every process the caller is permitted to signal, and
+/// any other negative number `-pgid` sends it to every process in the
+/// process group `pgid`.
+///
+/// # Example
+///
+/// ```no_run
+/// use chan_signal::Signal;
+///
+/// let children = vec![1234, 1235];
+/// let signal = chan_signal::notify(&[Signal::TERM]);
+/// for sig in signal.iter() {
+///     let results = chan_signal::forward_to(sig, &children);
+///     for (pid, res) in children.iter().zip(results) {
+///         if let Err(err) = res {
+///             eprintln!("failed to send {} to {}: {}", sig, pid, err);
+///         }
+///     }
+/// }
+/// ```
+pub fn forward_to(signal: Signal, pids: &[i32]) -> Vec<io::Result<()>> {
+    pids.iter().map(|&pid| {
+        if unsafe { kill(pid, signal.as_sig()) } != 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }).collect()
+}
+
+/// Call the signal handler that was installed before this crate took over
+/// a signal.
+///
+/// Blocking a signal (which `notify` and friends do) means that a handler
+/// installed by someone else, e.g., a C library or another runtime, never
+/// runs. The handler that was installed when this crate first blocked
+/// `signal` is recorded, and this calls it on the current thread as if
+/// `signal` had been delivered to it. This is typically called right after
+/// receiving `signal` on a channel. See `chain_previous` to have the watcher
+/// thread do it automatically.
+///
+/// Returns `false` if there was nothing to call, i.e., this crate hasn't
+/// blocked `signal` or the previous disposition was `SIG_DFL` or `SIG_IGN`.
+///
+/// Under the `sigwait` model, the signal isn't actually delivered to a
+/// handler, so not every handler can be chained. An error is returned if:
+///
+/// * The handler was installed with `SA_SIGINFO`, since there is no
+///   `siginfo_t` or `ucontext_t` to give it.
+/// * `signal` is one of `SEGV`, `BUS`, `ILL` or `FPE`. When these are
+///   caused by a fault, the kernel sends them to the faulting thread, which
+///   can't wait for them. (Blocking them is a bad idea to begin with.)
+pub fn call_previous(signal: Signal) -> io::Result<bool> {
+    let act = match lock(&PREVIOUS).get(&signal.as_sig()) {
+        None => return Ok(false),
+        Some(act) => *act,
+    };
+    check_chainable(signal, &act)?;
+    if act.sa_sigaction == libc::SIG_DFL || act.sa_sigaction == libc::SIG_IGN {
+        return Ok(false);
+    }
+    let handler: extern "C" fn(Sig) =
+        unsafe { mem::transmute(act.sa_sigaction) };
+    handler(signal.as_sig());
+    Ok(true)
+}
+
+/// Automatically call the previous handler of each of the given signals
+/// after it has been sent to its subscribers.
+///
+/// The previous handler is called on the watcher thread, after every channel
+/// subscribed to the signal. See `call_previous` for what is meant by the
+/// previous handler. If this crate hasn't blocked a signal yet, the handler
+/// installed right now is recorded as its previous handler.
+///
+/// An error is returned if the previous handler of any of the given signals
+/// can't be chained, as described in `call_previous`. In that case, nothing
+/// is changed.
+pub fn chain_previous(signals: &[Signal]) -> io::Result<()> {
+    let mut previous = lock(&PREVIOUS);
+    // Check every signal before recording anything, so that an error leaves
+    // `PREVIOUS` as it was.
+    let mut acts = vec![];
+    for &signal in signals {
+        let act = match previous.get(&signal.as_sig()) {
+            Some(act) => *act,
+            None => get_disposition(signal.as_sig())?,
+        };
+        check_chainable(signal, &act)?;
+        acts.push((signal.as_sig(), act));
+    }
+    let mut chained = lock(&CHAINED);
+    for (sig, act) in acts {
+        previous.entry(sig).or_insert(act);
+        chained.insert(sig as usize);
+    }
+    Ok(())
+}
+
+/// Returns an error if the handler in `act` can't be called from outside
+/// of a signal handler.
+fn check_chainable(signal: Signal, act: &libc::sigaction) -> io::Result<()> {
+    match signal {
+        Signal::SEGV | Signal::BUS | Signal::ILL | Signal::FPE => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot chain the handler of {:?}", signal),
+            ));
+        }
+        _ => {}
+    }
+    let custom = act.sa_sigaction != libc::SIG_DFL
+                 && act.sa_sigaction != libc::SIG_IGN;
+    if custom && act.sa_flags & libc::SA_SIGINFO != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot chain the SA_SIGINFO handler of {:?}", signal),
+        ));
+    }
+    Ok(())
+}
+
+/// Block the given signals and return a `signalfd` for reading them.
+///
+/// This is an alternative to `notify` for programs that already have an
+/// event loop (e.g., one built on `epoll`). The file descriptor returned
+/// becomes readable whenever one of the given signals is pending, and each
+/// `read` yields one or more `libc::signalfd_siginfo` records. See
+/// `signalfd(2)` for details. The descriptor is created with `SFD_CLOEXEC`
+/// and it is the caller's responsibility to close it.
+///
+/// No watcher thread is started by this function. However, once `notify` or
+/// `notify_on` has been called, the watcher thread waits on *every*
+/// subscribable signal that is blocked, so it will race with reads on this
+/// descriptor. Therefore, this should not be mixed with the channel based
+/// API in the same process.
+///
+/// This is only available on Linux and Android.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.** Otherwise, those threads will not have the given signals
+/// blocked and may receive them directly.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn signal_fd(signals: &[Signal]) -> io::Result<RawFd> {
+    let mut set = SigSet::empty();
+    for &signal in signals {
+        set.add(signal.as_sig())?;
+    }
+    save_mask()?;
+    set.thread_block_signals()?;
+    set.signal_fd()
+}
+
+/// A subscriber that isn't a plain channel. It is called on the watcher
+/// thread, so it must not block. It returns false once it has nowhere left
+/// to send signals (e.g., its receiver was dropped), after which it is
+/// removed. It is also removed if it panics.
+type Callback = Box<dyn FnMut(Signal) -> bool + Send>;
+
+/// A callback's subscription to a set of signals.
+struct CallbackSub {
+    /// Identifies the subscription for `unsubscribe_callback`.
+    id: usize,
+    sigs: BitSet,
+    /// Shared with the watcher thread while it calls the callback.
+    callback: Arc<Mutex<Callback>>,
+}
+
+/// Subscribe a callback to the given signals and block them.
+///
+/// Returns an id that can be passed to `unsubscribe_callback`.
+fn subscribe_callback(signals: &[Signal], callback: Callback) -> usize {
+    for &signal in signals {
+        check_subscribable(signal).unwrap();
+    }
+    start_watcher();
+    let mut sigs = BitSet::new();
+    for &signal in signals {
+        sigs.insert(signal.as_sig() as usize);
+    }
+    let id = NEXT_CALLBACK_ID.fetch_add(1, Ordering::SeqCst);
+    lock(&CALLBACKS).push(CallbackSub {
+        id,
+        sigs,
+        callback: Arc::new(Mutex::new(callback)),
+    });
+    block(signals);
+    id
+}
+
+/// Remove the callback with the given id.
+///
+/// Signals that are left without any subscribers are handed back to their
+/// default action.
+fn unsubscribe_callback(id: usize) {
+    let subs = lock(&HANDLERS);
+    let mut callbacks = lock(&CALLBACKS);
+    let sigs = match callbacks.iter().position(|cb| cb.id == id) {
+        None => return,
+        Some(i) => callbacks.remove(i).sigs,
+    };
+    for sig in sigs.iter() {
+        default_if_unsubscribed(&subs, &callbacks, sig as Sig);
+    }
+}
+
+/// A channel's subscription to a set of signals.
+struct Subscription {
+    sigs: BitSet,
+    /// When set, the channel is unsubscribed after its first delivery.
+    once: bool,
+    /// The number of signals that couldn't be sent because the channel
+    /// wasn't ready. It's shared with `RECEIVERS`.
+    dropped: Arc<AtomicU64>,
+}
+
+impl Subscription {
+    fn new(sigs: BitSet) -> Subscription {
+        Subscription {
+            sigs,
+            once: false,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+/// Subscribe `chan` to `signals` in `subs`, which is one of the maps of
+/// channels that aren't plain `Sender<Signal>`s.
+fn subscribe<T>(
+    subs: &mut HashMap<Sender<T>, Subscription>,
+    chan: &Sender<T>,
+    signals: &SignalSet,
+) {
+    let sub = subs.entry(chan.clone())
+        .or_insert_with(|| Subscription::new(BitSet::new()));
+    for signal in signals.iter() {
+        sub.sigs.insert(signal.as_sig() as usize);
+    }
+}
+
+/// Remove `signals` from every subscription in `subs`, and drop the
+/// channels that are left without any signals.
+fn remove_signals<T>(
+    subs: &mut HashMap<Sender<T>, Subscription>,
+    signals: &[Signal],
+) {
+    for &signal in signals {
+        let sig = signal.as_sig() as usize;
+        for sub in subs.values_mut() {
+            sub.sigs.remove(sig);
+        }
+    }
+    subs.retain(|_, sub| !sub.sigs.is_empty());
+}
+
+/// Send `msg` on every channel in `subs` that is subscribed to `signal`,
+/// retrying full channels as set by `set_fan_out`.
+///
+/// `subs` is only locked while the channels are copied and while their
+/// drop counts are updated, not while sending. `kind` names the channels in
+/// log messages.
+fn send_all<T: Clone>(
+    kind: &str,
+    subs: &Mutex<HashMap<Sender<T>, Subscription>>,
+    signal: Signal,
+    msg: T,
+) {
+    let chans = subscribed(&lock(subs), signal.as_sig());
+    let attempts = fan_out_attempts(signal);
+    let dropped = send_each(kind, &chans, signal, msg, attempts);
+    count_dropped(subs, dropped);
+}
+
+/// Returns a copy of every channel in `subs` that is subscribed to `sig`.
+fn subscribed<T>(
+    subs: &HashMap<Sender<T>, Subscription>,
+    sig: Sig,
+) -> Vec<Sender<T>> {
+    subs.iter()
+        .filter(|&(_, sub)| sub.sigs.contains(sig as usize))
+        .map(|(s, _)| s.clone())
+        .collect()
+}
+
+/// Send `msg` on each of `chans` without blocking, and return the channels
+/// that it had to be dropped for.
+///
+/// The channels that are full are tried again up to `attempts` times,
+/// `FAN_OUT_RETRY_DELAY` apart.
+fn send_each<'a, T: Clone>(
+    kind: &str,
+    chans: &'a [Sender<T>],
+    signal: Signal,
+    msg: T,
+    attempts: u32,
+) -> Vec<&'a Sender<T>> {
+    let sig = signal.as_sig();
+    let mut full: Vec<(usize, &Sender<T>)> =
+        chans.iter().enumerate().collect();
+    for attempt in 0..=attempts {
+        if attempt > 0 {
+            log_trace!("retrying {} for {} full {}s",
+                       signal, full.len(), kind);
+            thread::sleep(FAN_OUT_RETRY_DELAY);
+        }
+        let mut still_full = vec![];
+        for (i, s) in full {
+            let mut sent = false;
+            chan_select! {
+                default => {},
+                s.send(msg.clone()) => {
+                    sent = true;
+                },
+            }
+            if sent {
+                log_trace!("delivered {} to {} {}", signal, kind, i);
+                count_stat(&DELIVERED, sig);
+            } else {
+                still_full.push((i, s));
+            }
+        }
+        full = still_full;
+        if full.is_empty() {
+            break;
+        }
+    }
+    let mut dropped = vec![];
+    for (i, s) in full {
+        log_debug!("dropped {} for {} {}", signal, kind, i);
+        count_stat(&DROPPED, sig);
+        dropped.push(s);
+    }
+    dropped
+}
+
+/// Add one to the drop count of each of the given channels that is still in
+/// `subs`.
+fn count_dropped<T>(
+    subs: &Mutex<HashMap<Sender<T>, Subscription>>,
+    dropped: Vec<&Sender<T>>,
+) {
+    if dropped.is_empty() {
+        return;
+    }
+    let subs = lock(subs);
+    for s in dropped {
+        if let Some(sub) = subs.get(s) {
+            sub.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Unsubscribe `chan` from `sig`. If the channel is left without any
+/// signals, then this crate's copy of it is dropped.
+fn unsubscribe(
+    subs: &mut HashMap<Sender<Signal>, Subscription>,
+    chan: &Sender<Signal>,
+    sig: usize,
+) {
+    let empty = match subs.get_mut(chan) {
+        None => return,
+        Some(sub) => {
+            sub.sigs.remove(sig);
+            sub.sigs.is_empty()
+        }
+    };
+    if empty {
+        subs.remove(chan);
+    }
+}
+
+/// Returns true if any channel or callback is subscribed to `sig`.
+fn is_subscribed(
+    subs: &HashMap<Sender<Signal>, Subscription>,
+    callbacks: &[CallbackSub],
+    sig: usize,
+) -> bool {
+    subs.values().any(|sub| sub.sigs.contains(sig))
+    || callbacks.iter().any(|cb| cb.sigs.contains(sig))
+    || lock(&EVENT_HANDLERS)
+           .values().any(|sub| sub.sigs.contains(sig))
+    || lock(&INFO_HANDLERS)
+           .values().any(|sub| sub.sigs.contains(sig))
+    || lock(&BATCH_HANDLERS)
+           .values().any(|sub| sub.sigs.contains(sig))
+}
+
+/// Hand `sig` back to its default action if nothing is subscribed to it.
+///
+/// We can't use `restore_default` here since the watcher thread must keep
+/// every signal blocked. Instead, the watcher thread re-raises the signal
+/// when it receives it.
+fn default_if_unsubscribed(
+    subs: &HashMap<Sender<Signal>, Subscription>,
+    callbacks: &[CallbackSub],
+    sig: Sig,
+) {
+    if !is_subscribed(subs, callbacks, sig as usize) {
+        let _ = set_disposition(sig, libc::SIG_DFL);
+        lock(&DEFAULTED).insert(sig as usize);
+    }
+}
+
+/// Spawns the watcher thread. See `set_spawn_hook`.
+type SpawnHook =
+    Box<dyn Fn(Box<dyn FnMut() + Send>) -> io::Result<()> + Send + Sync>;
+
+/// A handle to the running watcher thread.
+struct Watcher {
+    /// The watcher thread's `pthread_t`, which isn't `Send` everywhere.
+    thread: usize,
+    /// Receives whether the watcher thread panicked once it exits.
+    done: mpsc::Receiver<bool>,
+    /// When set, the watcher exits the next time it wakes up.
+    quit: Arc<AtomicBool>,
+    /// When set, the watcher recomputes the set of signals it waits on the
+    /// next time it wakes up.
+    refresh: Arc<AtomicBool>,
+    /// Cleared when the watcher thread exits, for whatever reason.
+    alive: Arc<AtomicBool>,
+}
+
+impl Watcher {
+    fn pthread(&self) -> libc::pthread_t {
+        self.thread as libc::pthread_t
+    }
+}
+
+/// Lives on the watcher thread's stack. If the thread exits while it is
+/// still the current watcher (i.e., not because of `shutdown`), a new
+/// watcher is started to replace it.
+struct WatcherGuard {
+    alive: Arc<AtomicBool>,
+    done: mpsc::Sender<bool>,
+}
+
+impl WatcherGuard {
+    /// Record the error that makes the watcher thread stop, and stop being
+    /// the current watcher so that it isn't replaced right away. The next
+    /// subscription starts a new one.
+    fn give_up(&self, err: io::Error) {
+        log_warn!("giving up on sigwait: {}", err);
+        let mut watcher = lock(&WATCHER);
+        let current = match *watcher {
+            Some(ref w) => Arc::ptr_eq(&w.alive, &self.alive),
+            None => false,
+        };
+        if current {
+            *watcher = None;
+        }
+        *lock(&WATCHER_ERROR) = Some(err);
+    }
+}
+
+impl Drop for WatcherGuard {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::SeqCst);
+        let _ = self.done.send(thread::panicking());
+        let mut watcher = lock(&WATCHER);
+        let current = match *watcher {
+            Some(ref w) => Arc::ptr_eq(&w.alive, &self.alive),
+            None => false,
+        };
+        if current {
+            log_warn!("watcher thread died, starting a new one");
+            // If this fails, the next subscription tries again.
+            match init() {
+                Ok(new) => *watcher = Some(new),
+                Err(err) => log_warn!("failed to restart watcher: {}", err),
+            }
+        }
+    }
+}
+
+/// The number of unexpected errors in a row from `sigwait` after which the
+/// watcher thread gives up. See `watcher_error`.
+const MAX_WAIT_FAILURES: usize = 100;
+
+/// Start the watcher thread if it isn't already running.
+///
+/// This panics if the thread can't be started.
+fn start_watcher() {
+    try_start_watcher().unwrap();
+}
+
+/// Start the watcher thread if it isn't already running, and return an
+/// error if it can't be started.
+fn try_start_watcher() -> io::Result<()> {
+    let mut watcher = lock(&WATCHER);
+    let running = match *watcher {
+        Some(ref w) => w.alive.load(Ordering::SeqCst),
+        None => false,
+    };
+    if !running {
+        *watcher = Some(init()?);
+    }
+    Ok(())
+}
+
+/// Make the watcher thread, if it's running, start waiting on any realtime
+/// signals that have been blocked since it last looked.
+fn refresh_watcher() {
+    if let Some(ref watcher) = *lock(&WATCHER) {
+        watcher.refresh.store(true, Ordering::SeqCst);
+        // See `shutdown`. The watcher always waits on `URG`.
+        unsafe {
+            libc::pthread_kill(watcher.pthread(), SIGURG);
+        }
+    }
+}
+
+fn init() -> io::Result<Watcher> {
+    // First:
+    // Get the curren thread_mask. (We cannot just overwrite the threadmask with
+    // an empty one because this function is executed lazily.
+    let saved_mask = SigSet::current()?;
+    save_mask()?;
+
+    // Then:
+    // Block all signals in this thread. The signal mask will then be inherited
+    // by the worker thread.
+    // Realtime signals are blocked too, so that they stay pending until the
+    // watcher starts waiting on them.
+    SigSet::watcher_mask().thread_set_signal_mask()?;
+    let name = lock(&WATCHER_NAME).clone();
+    let quit = Arc::new(AtomicBool::new(false));
+    let thread_quit = quit.clone();
+    let refresh = Arc::new(AtomicBool::new(false));
+    let thread_refresh = refresh.clone();
+    let alive = Arc::new(AtomicBool::new(true));
+    let thread_alive = alive.clone();
+    let (started_send, started) = mpsc::channel();
+    let (done_send, done) = mpsc::channel();
+    let body = move || {
+        let guard = WatcherGuard { alive: thread_alive, done: done_send };
+        // A thread spawned by a hook doesn't necessarily inherit the mask
+        // set below.
+        if let Err(err) = SigSet::watcher_mask().thread_set_signal_mask() {
+            log_warn!("failed to set watcher mask: {}", err);
+        }
+        let _ = started_send.send(unsafe { libc::pthread_self() } as usize);
+        let mut waiter = match Waiter::new() {
+            Ok(waiter) => waiter,
+            Err(err) => {
+                guard.give_up(err);
+                return;
+            }
+        };
+        let mut listen = SigSet::watched();
+        // The number of unexpected errors from `sigwait` in a row.
+        let mut failures = 0;
+
+        loop {
+            let (info, count, received_at) = match waiter.wait(&mut listen) {
+                Ok((info, count)) => {
+                    log_trace!("sigwait returned signal {} ({})",
+                               info.signal.as_sig(), info.signal);
+                    if count > 1 {
+                        log_trace!("{} was sent {} times", info.signal, count);
+                    }
+                    failures = 0;
+                    (info, count, Instant::now())
+                }
+                Err(err) => {
+                    // Some platforms return `EINTR` or `EAGAIN`, which just
+                    // mean we should try again. Nothing else is expected, but
+                    // giving up right away would silently stop all signal
+                    // delivery, so start over with a fresh set and try again
+                    // after a short pause (to avoid spinning on a persistent
+                    // error).
+                    let transient = err.kind() == io::ErrorKind::Interrupted
+                        || err.raw_os_error() == Some(libc::EAGAIN);
+                    if transient {
+                        continue;
+                    }
+                    log_warn!("sigwait failed: {}", err);
+                    failures += 1;
+                    if failures == MAX_WAIT_FAILURES {
+                        guard.give_up(err);
+                        break;
+                    }
+                    listen = SigSet::watched();
+                    thread::sleep(Duration::from_millis(10));
+                    continue;
+                }
+            };
+            if thread_quit.load(Ordering::SeqCst) {
+                log_debug!("watcher thread stopping");
+                break;
+            }
+            let refreshed = thread_refresh.swap(false, Ordering::SeqCst);
+            if refreshed {
+                listen = SigSet::watched();
+            }
+            if is_wakeup(&info, refreshed) {
+                continue;
+            }
+            if PANIC_WATCHER.swap(false, Ordering::SeqCst) {
+                panic!("chan-signal: watcher panic requested");
+            }
+            // Receive everything else that's already pending too, so that
+            // channels subscribed with `notify_batched` get it all at once,
+            // and so that higher priority signals are delivered first.
+            let mut received = vec![(info, received_at); count];
+            let priorities = lock(&PRIORITIES).clone();
+            if !priorities.is_empty() || !lock(&BATCH_HANDLERS).is_empty() {
+                received.extend(
+                    drain_pending(&mut waiter, &mut listen, &thread_refresh));
+            }
+            if !priorities.is_empty() {
+                // The sort is stable, so signals with the same priority
+                // stay in the order they were received.
+                received.sort_by_key(|(info, _)| {
+                    let sig = info.signal.as_sig();
+                    cmp::Reverse(priorities.get(&sig).cloned().unwrap_or(0))
+                });
+            }
+            let mut batch = vec![];
+            for (info, received_at) in received {
+                // A panic in here (e.g., from a bug) loses this one signal,
+                // but mustn't stop the delivery of all the others.
+                let sig = info.signal;
+                let delivered = panic::catch_unwind(AssertUnwindSafe(|| {
+                    deliver(info, received_at)
+                }));
+                match delivered {
+                    Ok(true) => batch.push(sig),
+                    Ok(false) => {}
+                    Err(_) => log_warn!("panicked while delivering {}", sig),
+                }
+            }
+            if !batch.is_empty() {
+                let delivered = panic::catch_unwind(AssertUnwindSafe(|| {
+                    deliver_batch(&batch)
+                }));
+                if delivered.is_err() {
+                    log_warn!("panicked while delivering a batch");
+                }
+            }
+        }
+    };
+    let spawned = match *lock(&SPAWN_HOOK) {
+        None => thread::Builder::new().name(name).spawn(body).map(|_| ()),
+        Some(ref hook) => {
+            let mut body = Some(body);
+            hook(Box::new(move || {
+                if let Some(body) = body.take() {
+                    body();
+                }
+            }))
+        }
+    };
+    // Wait for the thread to set its mask, so that signals blocked after
+    // this returns are guaranteed to reach it.
+    let thread = spawned.and_then(|()| started.recv().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::Other, "chan-signal watcher thread didn't start")
+    }));
+    let thread = match thread {
+        Ok(thread) => {
+            log_debug!("watcher thread started");
+            thread
+        }
+        Err(err) => {
+            // Don't leave the calling thread with every signal blocked.
+            saved_mask.thread_set_signal_mask()?;
+            return Err(err);
+        }
+    };
+
+    // Now:
+    // Reset to the previously saved sigmask.
+    // This whole procedure is necessary, as we cannot rely on the worker thread
+    // starting fast enough to set its signal mask. Otherwise an early SIGTERM or
+    // similar may take down the process even though the main thread has blocked
+    // the signal.
+    saved_mask.thread_set_signal_mask()?;
+    Ok(Watcher {
+        thread,
+        done,
+        quit,
+        refresh,
+        alive,
+    })
+}
+
+/// The most signals received at once for `notify_batched`, in case they
+/// keep arriving faster than the watcher thread can receive them.
+const MAX_BATCH: usize = 1024;
+
+/// Receive every signal in `listen` that is already pending, without
+/// blocking.
+///
+/// Like the watcher thread's own wait, a `URG` sent by `refresh_watcher`
+/// only makes `listen` start over, and isn't returned.
+fn drain_pending(
+    waiter: &mut Waiter,
+    listen: &mut SigSet,
+    refresh: &AtomicBool,
+) -> Vec<(SignalInfo, Instant)> {
+    let mut drained = vec![];
+    while drained.len() < MAX_BATCH {
+        let (info, count) = match waiter.poll(listen) {
+            Ok(Some(received)) => received,
+            Ok(None) | Err(_) => break,
+        };
+        log_trace!("drained pending signal {}", info.signal);
+        let refreshed = refresh.swap(false, Ordering::SeqCst);
+        if refreshed {
+            *listen = SigSet::watched();
+        }
+        if is_wakeup(&info, refreshed) {
+            continue;
+        }
+        let received_at = Instant::now();
+        drained.extend(iter::repeat((info, received_at)).take(count));
+    }
+    drained
+}
+
+/// If a thread is waiting for `sig` in `notify_until`, send it to that
+/// thread and return true.
+///
+/// The watcher only receives such a signal if it arrived before the watcher
+/// stopped waiting on it, or if it is `URG`.
+fn hand_over(sig: Sig) -> bool {
+    let waiting = lock(&WAITING);
+    match waiting.iter().find(|w| w.1.contains(sig as usize)) {
+        None => false,
+        Some(&(thread, _)) => {
+            log_debug!("handing {} over to notify_until", Signal::new(sig));
+            unsafe {
+                libc::pthread_kill(thread as libc::pthread_t, sig);
+            }
+            true
+        }
+    }
+}
+
+/// Returns true if `info` is the `URG` that `refresh_watcher` sends to wake
+/// up the watcher thread, which must not be delivered.
+///
+/// On Linux and Android, `sigwaitinfo` says who sent a signal, so a `URG`
+/// from another process or from the kernel is always delivered. The wakeup
+/// is sent with `pthread_kill`, which is reported as `SI_TKILL`, but some
+/// kernels report it as `SI_USER` like `kill`. So a `URG` this process sent
+/// with `kill` is only taken to be the wakeup if a refresh was requested.
+/// Elsewhere, any `URG` received after a refresh was requested is.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn is_wakeup(info: &SignalInfo, refreshed: bool) -> bool {
+    if info.signal.as_sig() != SIGURG
+        || info.pid != Some(unsafe { getpid() }) {
+        return false;
+    }
+    info.code == libc::SI_TKILL || (info.code == libc::SI_USER && refreshed)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn is_wakeup(info: &SignalInfo, refreshed: bool) -> bool {
+    info.signal.as_sig() == SIGURG && refreshed
+}
+
+/// Waits for signals with `sigwait`.
+///
+/// With the `kqueue` feature, this is replaced on macOS and the BSDs by a
+/// waiter that uses kqueue's `EVFILT_SIGNAL` filter, which also reports how
+/// many times a signal was sent while it was pending.
+#[cfg(not(all(feature = "kqueue", any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+))))]
+struct Waiter;
+
+#[cfg(not(all(feature = "kqueue", any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+))))]
+impl Waiter {
+    fn new() -> io::Result<Waiter> {
+        Ok(Waiter)
+    }
+
+    /// Wait for a signal in `listen`, and return it along with the number
+    /// of times it was sent. `sigwait` can't tell, so that's always `1`.
+    fn wait(
+        &mut self,
+        listen: &mut SigSet,
+    ) -> io::Result<(SignalInfo, usize)> {
+        listen.wait().map(|info| (info, 1))
+    }
+
+    /// Like `wait`, but returns `None` instead of blocking.
+    fn poll(
+        &mut self,
+        listen: &mut SigSet,
+    ) -> io::Result<Option<(SignalInfo, usize)>> {
+        Ok(listen.poll()?.map(|info| (info, 1)))
+    }
+}
+
+#[cfg(all(feature = "kqueue", any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly",
+    target_os = "netbsd", target_os = "openbsd",
+)))]
+use self::kqueue::Waiter;
+
+/// Send a signal received by the watcher thread to all of its subscribers.
+///
+/// Returns false if it was handed back to the operating system instead,
+/// in which case it isn't part of a batch either.
+fn deliver(info: SignalInfo, received_at: Instant) -> bool {
+    let sig = info.signal.as_sig();
+    if hand_over(sig) {
+        return false;
+    }
+    LAST_SIGNAL.store(sig as usize, Ordering::Relaxed);
+    count_stat(&RECEIVED, sig);
+    record_history(info.signal);
+    if let Signal::Other(_) = info.signal {
+        log_warn!("received unexpected signal number {}", sig);
+    }
+    if lock(&DEFAULTED).contains(sig as usize) {
+        // Whoever handed the signal back already installed its disposition.
+        log_debug!("re-raising handed back signal {}", info.signal);
+        raise_here(sig);
+        return false;
+    }
+    if escalate(sig) {
+        log_debug!("re-raising {} with its default action", info.signal);
+        raise_default_here(sig);
+        return false;
+    }
+    let seq = NEXT_SEQ.fetch_add(1, Ordering::SeqCst);
+    let chans = {
+        let mut subs = lock(&HANDLERS);
+        let chans = subscribed(&subs, sig);
+        // One-shot subscriptions are removed before anything is sent, so
+        // that they're sent at most one signal. Every signal of a removed
+        // subscription may be left without subscribers, not just this one.
+        let mut unsubscribed = BitSet::new();
+        for s in &chans {
+            if subs[s].once {
+                unsubscribed.union_with(&subs.remove(s).unwrap().sigs);
+            }
+        }
+        if !unsubscribed.is_empty() {
+            let callbacks = lock(&CALLBACKS);
+            for sig in unsubscribed.iter() {
+                default_if_unsubscribed(&subs, &callbacks, sig as Sig);
+            }
+        }
+        chans
+    };
+    let chans = hold_back_paused(chans, info.signal);
+    let attempts = fan_out_attempts(info.signal);
+    let dropped = send_each(
+        "channel", &chans, info.signal, Signal::new(sig), attempts);
+    count_dropped(&HANDLERS, dropped);
+    let event = SignalEvent {
+        signal: info.signal,
+        received_at,
+        seq,
+        value: info.value,
+    };
+    send_all("event channel", &EVENT_HANDLERS, info.signal, event);
+    send_all("info channel", &INFO_HANDLERS, info.signal, info);
+    // The callbacks are called without the registry locked, so that they
+    // may subscribe, unsubscribe or drop a subscription themselves.
+    let subscribed: Vec<(usize, Arc<Mutex<Callback>>)> = lock(&CALLBACKS)
+        .iter()
+        .filter(|cb| cb.sigs.contains(sig as usize))
+        .map(|cb| (cb.id, cb.callback.clone()))
+        .collect();
+    let mut dead = vec![];
+    for (id, callback) in subscribed {
+        // An earlier callback may have removed this one.
+        if !lock(&CALLBACKS).iter().any(|cb| cb.id == id) {
+            continue;
+        }
+        let mut callback = lock(&callback);
+        let call = AssertUnwindSafe(|| (*callback)(Signal::new(sig)));
+        match panic::catch_unwind(call) {
+            Ok(true) => {
+                log_trace!("delivered {} to callback {}", sig, id);
+            }
+            Ok(false) | Err(_) => {
+                log_debug!("removing callback {} after {}", id, sig);
+                dead.push(id);
+            }
+        }
+    }
+    if !dead.is_empty() {
+        lock(&CALLBACKS).retain(|cb| !dead.contains(&cb.id));
+    }
+    if lock(&CHAINED).contains(sig as usize) {
+        let _ = call_previous(Signal::new(sig));
+    }
+    true
+}
+
+/// Send each channel subscribed with `notify_batched` the signals in
+/// `batch` that it is subscribed to, in order.
+fn deliver_batch(batch: &[Signal]) {
+    let chans: Vec<(Sender<Vec<Signal>>, Vec<Signal>)> = {
+        let subs = lock(&BATCH_HANDLERS);
+        subs.iter().filter_map(|(s, sub)| {
+            let sigs: Vec<Signal> = batch.iter().cloned()
+                .filter(|sig| sub.sigs.contains(sig.as_sig() as usize))
+                .collect();
+            if sigs.is_empty() { None } else { Some((s.clone(), sigs)) }
+        }).collect()
+    };
+    let mut dropped = vec![];
+    for (i, (chan, sigs)) in chans.iter().enumerate() {
+        let mut sent = false;
+        chan_select! {
+            default => {},
+            chan.send(sigs.clone()) => {
+                sent = true;
+            },
+        }
+        if sent {
+            log_trace!("delivered {:?} to batch channel {}", sigs, i);
+        } else {
+            log_debug!("dropped {:?} for batch channel {}", sigs, i);
+            dropped.push(chan);
+        }
+        for sig in sigs {
+            count_stat(if sent { &DELIVERED } else { &DROPPED }, sig.as_sig());
+        }
+    }
+    count_dropped(&BATCH_HANDLERS, dropped);
+}
+
+/// Hold back `signal` for each of `chans` that is paused, and return the
+/// channels that aren't.
+fn hold_back_paused(chans: Vec<Sender<Signal>>, signal: Signal)
+        -> Vec<Sender<Signal>> {
+    let mut paused = lock(&PAUSED);
+    if paused.is_empty() {
+        return chans;
+    }
+    let mut dropped = vec![];
+    let chans = chans.into_iter().filter(|s| {
+        match paused.get_mut(s) {
+            None => true,
+            Some(held) => {
+                if held.len() < MAX_PAUSED {
+                    held.push(signal);
+                } else {
+                    count_stat(&DROPPED, signal.as_sig());
+                    dropped.push(s.clone());
+                }
+                false
+            }
+        }
+    }).collect();
+    drop(paused);
+    count_dropped(&HANDLERS, dropped.iter().collect());
+    chans
+}
+
+/// Returns true if `sig` is subscribed with `notify_with_escalation` and has
+/// already been delivered once. Otherwise, it is recorded as delivered.
+fn escalate(sig: Sig) -> bool {
+    if !lock(&ESCALATING).contains(sig as usize) {
+        return false;
+    }
+    !lock(&ESCALATION_ARMED).insert(sig as usize)
+}
+
+/// The write end of the pipe used by `observe_handler`, or `-1` if the
+/// observer thread hasn't been started. This is only written while holding
+/// the `OBSERVER_STARTED` lock and before any handler is installed.
+static mut OBSERVE_FD: libc::c_int = -1;
+
+lazy_static! {
+    static ref OBSERVER_STARTED: Mutex<bool> = Mutex::new(false);
+}
+
+/// The signal handler installed by `notify_observe`.
+///
+/// This must be async-signal-safe, so all it does is write the signal number
+/// to a pipe that is read by the observer thread.
+extern "C" fn observe_handler(sig: Sig) {
+    unsafe {
+        // Don't clobber errno for the code we've interrupted.
+        let saved_errno = *errno_location();
+        let byte = sig as u8;
+        // If the pipe is full, then the signal is dropped. There's nothing
+        // else we can do here.
+        libc::write(OBSERVE_FD, &byte as *const u8 as *const libc::c_void, 1);
+        *errno_location() = saved_errno;
+    }
+}
+
+/// Start the thread that reads signals written by `observe_handler` if it
+/// isn't already running.
+fn start_observer() -> io::Result<()> {
+    let mut started = lock(&OBSERVER_STARTED);
+    if *started {
+        return Ok(());
+    }
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let (rfd, wfd) = (fds[0], fds[1]);
+    unsafe {
+        libc::fcntl(rfd, libc::F_SETFD, libc::FD_CLOEXEC);
+        libc::fcntl(wfd, libc::F_SETFD, libc::FD_CLOEXEC);
+        // The handler must never block.
+        libc::fcntl(wfd, libc::F_SETFL, libc::O_NONBLOCK);
+    }
+    let spawned = thread::Builder::new()
+        .name("chan-signal-observe".to_string())
+        .spawn(move || loop {
+            let mut byte = 0u8;
+            let n = unsafe {
+                libc::read(rfd, &mut byte as *mut u8 as *mut libc::c_void, 1)
+            };
+            if n == 1 {
+                deliver(SignalInfo::bare(byte as Sig), Instant::now());
+                reraise_observed(byte as Sig);
+            }
+        });
+    if let Err(err) = spawned {
+        unsafe {
+            libc::close(rfd);
+            libc::close(wfd);
+        }
+        return Err(err);
+    }
+    unsafe { OBSERVE_FD = wfd; }
+    *started = true;
+    Ok(())
+}
+
+/// Re-raise an observed signal with its default disposition, and then
+/// re-install the pass-through handler.
+///
+/// The signal is sent to the calling thread while it is unblocked, so that
+/// the default action happens before the handler is re-installed.
+fn reraise_observed(sig: Sig) {
+    let observed = lock(&OBSERVED);
+    if !observed.contains(sig as usize) {
+        return;
+    }
+    raise_default_here(sig);
+    let handler = observe_handler as *const () as libc::sighandler_t;
+    let _ = set_disposition(sig, handler);
+}
+
+/// Raise `sig` in the calling thread with its default disposition.
+///
+/// The signal is unblocked while it is raised, so that the default action
+/// happens before this returns (if it returns at all). Afterwards, the
+/// signal is blocked in the calling thread.
+fn raise_default_here(sig: Sig) {
+    let _ = set_disposition(sig, libc::SIG_DFL);
+    raise_here(sig);
+}
+
+/// Raise `sig` in the calling thread with its current disposition.
+///
+/// Like `raise_default_here`, the signal is unblocked while it is raised.
+fn raise_here(sig: Sig) {
+    let mut set = SigSet::empty();
+    let _ = set.add(sig);
+    let _ = set.thread_unblock_signals();
+    unsafe { libc::pthread_kill(libc::pthread_self(), sig); }
+    let _ = set.thread_block_signals();
+}
+
+#[cfg(any(
+    target_os = "linux", target_os = "redox", target_os = "fuchsia"
+))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    __errno_location()
+}
+
+#[cfg(any(
+    target_os = "macos", target_os = "ios",
+    target_os = "freebsd", target_os = "dragonfly"
+))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    __error()
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "netbsd", target_os = "openbsd"
+))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    __errno()
+}
+
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    ___errno()
+}
+
+#[cfg(target_os = "haiku")]
+unsafe fn errno_location() -> *mut libc::c_int {
+    _errnop()
+}
+
+/// Kill the current process. (Only used in tests.)
+#[doc(hidden)]
+pub fn kill_this(sig: Signal) {
+    unsafe { kill(getpid(), sig.as_sig()); }
+}
+
+/// Make the watcher thread panic the next time it receives a signal, which
+/// is then lost. (Only used in tests.)
+#[doc(hidden)]
+pub fn panic_watcher() {
+    PANIC_WATCHER.store(true, Ordering::SeqCst);
+}
+
+/// Poison the mutex holding the channel subscriptions by panicking while it
+/// is locked. (Only used in tests.)
+#[doc(hidden)]
+pub fn poison_handlers() {
+    let _ = thread::spawn(|| {
+        let _subs = lock(&HANDLERS);
+        panic!("chan-signal: poisoning requested");
+    }).join();
+}
+
+/// Ask the watcher thread to refresh the signals it waits on without waking
+/// it up, so that the next signal it receives finds a refresh pending.
+/// (Only used in tests.)
+#[doc(hidden)]
+pub fn request_refresh() {
+    if let Some(ref watcher) = *lock(&WATCHER) {
+        watcher.refresh.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Queue a signal to the current process along with a value.
+///
+/// This uses `sigqueue`, so the value is received by channels subscribed
+/// with `notify_events`. If the signal is a realtime signal, then it is
+/// queued, i.e., sending it several times in a row delivers it (and each
+/// value) that many times.
+///
+/// An error is returned if the signal couldn't be sent. `sigqueue` is only
+/// used on Linux and Android. On other platforms, nothing is sent and an
+/// error of kind `ErrorKind::Other` is always returned, so fall back to
+/// `kill` (without a value) there if that's acceptable.
+pub fn queue_to_self(sig: Signal, value: usize) -> io::Result<()> {
+    queue(sig, value)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn queue(sig: Signal, value: usize) -> io::Result<()> {
+    let val = libc::sigval { sival_ptr: value as *mut libc::c_void };
+    if unsafe { libc::sigqueue(getpid(), sig.as_sig(), val) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn queue(_sig: Signal, _value: usize) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "sigqueue is not supported on this platform",
+    ))
+}
+
+/// Returns true if `signal` can be subscribed to on this platform.
+///
+/// Fuchsia's POSIX layer only delivers a handful of signals. The others are
+/// defined, but never arrive.
+#[cfg(target_os = "fuchsia")]
+fn platform_supports(signal: Signal) -> bool {
+    match signal.canonical() {
+        Signal::HUP | Signal::INT | Signal::TERM | Signal::ALRM
+        | Signal::USR1 | Signal::USR2 | Signal::CHLD => true,
+        _ => false,
+    }
+}
+
+#[cfg(not(target_os = "fuchsia"))]
+fn platform_supports(_signal: Signal) -> bool {
+    true
+}
+
+/// Safe wrapper around `sigset_t`.
+#[derive(Clone, Copy)]
+struct SigSet(sigset_t);
+
+impl SigSet {
+    // `sigset_t` is plain data, so a zeroed one is a valid value to hand to
+    // `sigemptyset` and friends. `sigemptyset` and `sigfillset` can only
+    // fail if they're given an invalid pointer, which is a bug here.
+
+    fn empty() -> SigSet {
+        let mut set = unsafe { mem::zeroed() };
+        let ecode = unsafe { sigemptyset(&mut set) };
+        assert_eq!(ecode, 0, "sigemptyset failed");
+        SigSet(set)
+    }
+
+    fn full() -> SigSet {
+        let mut set = unsafe { mem::zeroed() };
+        let ecode = unsafe { sigfillset(&mut set) };
+        assert_eq!(ecode, 0, "sigfillset failed");
+        SigSet(set)
+    }
+
+    fn current() -> io::Result<SigSet> {
+        let mut set = unsafe { mem::zeroed() };
+        let ecode = unsafe {
+            pthread_sigmask(SIG_SETMASK, ptr::null_mut(), &mut set)
+        };
+        mask_errno(SigSet(set), ecode)
+    }
+
+    /// Creates a new signal set with precisely the signals we're limited
+    /// to subscribing to.
+    ///
+    /// A signal that `sigaddset` rejects isn't supported on this platform,
+    /// so it is left out rather than causing a panic.
+    fn subscribable() -> SigSet {
+        let mut set = SigSet::empty();
+        let subscribable = |sig: &Signal| {
+            sig.is_catchable() && !sig.is_fault() && platform_supports(*sig)
+        };
+        for signal in Signal::iter().filter(subscribable) {
+            let _ = set.add(signal.as_sig());
+        }
+        set.without_excluded()
+    }
+
+    /// Creates a new signal set with the signals that the watcher thread
+    /// waits on, i.e., the subscribable signals and the realtime signals
+    /// that have been blocked, except for those a thread is waiting for in
+    /// `notify_until`. `URG` is always included, since it wakes up the
+    /// watcher.
+    fn watched() -> SigSet {
+        let mut set = SigSet::subscribable();
+        let realtime = lock(&REALTIME);
+        if let Some((min, max)) = realtime_range() {
+            for sig in min..max + 1 {
+                if realtime.contains(sig) {
+                    let _ = set.add(sig);
+                }
+            }
+        }
+        for (_, sigs) in lock(&WAITING).iter() {
+            for sig in sigs.iter() {
+                if sig as Sig != SIGURG {
+                    let _ = set.remove(sig as Sig);
+                }
+            }
+        }
+        set
+    }
+
+    /// Creates a new signal set with the signals that are blocked in the
+    /// watcher thread, i.e., the subscribable and all realtime signals.
+    fn watcher_mask() -> SigSet {
+        let mut set = SigSet::subscribable();
+        if let Some((min, max)) = realtime_range() {
+            for sig in min..max + 1 {
+                let _ = set.add(sig);
+            }
+        }
+        set.without_excluded()
+    }
+
+    /// Removes the signals excluded with `set_excluded` from this set.
+    fn without_excluded(mut self) -> SigSet {
+        for sig in lock(&EXCLUDED).iter() {
+            let _ = self.remove(sig as Sig);
+        }
+        self
+    }
+
+    fn add(&mut self, sig: Sig) -> io::Result<()> {
+        unsafe { ok_errno((), sigaddset(&mut self.0, sig)) }
+    }
+
+    /// Like `add`, but reports the error as `SignalError::Unsupported`.
+    /// (`sigaddset` can only fail if the signal is invalid.)
+    fn add_signal(&mut self, signal: Signal) -> io::Result<()> {
+        self.add(signal.as_sig())
+            .map_err(|_| SignalError::Unsupported(signal).into())
+    }
+
+    fn remove(&mut self, sig: Sig) -> io::Result<()> {
+        unsafe { ok_errno((), sigdelset(&mut self.0, sig)) }
+    }
+
+    fn contains(&self, sig: Sig) -> bool {
+        unsafe { sigismember(&self.0, sig) == 1 }
+    }
+
+    /// Returns the signals in this set that are pending, or `None` if there
+    /// aren't any (or they can't be determined).
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn pending_in(&self) -> Option<SigSet> {
+        let pending = SigSet::pending().ok()?;
+        let realtime = realtime_range()
+            .map_or(0..0, |(min, max)| min..max + 1);
+        let mut set = SigSet::empty();
+        let mut any = false;
+        let sigs = Signal::iter().map(|sig| sig.as_sig()).chain(realtime);
+        for sig in sigs {
+            if self.contains(sig) && pending.contains(sig) {
+                let _ = set.add(sig);
+                any = true;
+            }
+        }
+        if any { Some(set) } else { None }
+    }
+
+    fn pending() -> io::Result<SigSet> {
+        let mut set = unsafe { mem::zeroed() };
+        if unsafe { sigpending(&mut set) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(SigSet(set))
+    }
+
+    /// Returns the subscribable signals in this set.
+    fn signals(&self) -> Vec<Signal> {
+        Signal::iter().filter(|sig| self.contains(sig.as_sig())).collect()
+    }
+
+    /// Wait for a signal in this set, and return it along with its
+    /// `siginfo_t`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn wait(&mut self) -> io::Result<SignalInfo> {
+        let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+        let sig = unsafe { sigwaitinfo(&self.0, &mut info) };
+        if sig < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(SignalInfo::from_siginfo(sig, &info))
+    }
+
+    /// Wait for a signal in this set. There's no `sigwaitinfo` on this
+    /// platform, so nothing but the signal is known.
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn wait(&mut self) -> io::Result<SignalInfo> {
+        let mut sig: Sig = 0;
+        let errno = unsafe { sigwait(&mut self.0, &mut sig) };
+        ok_errno(SignalInfo::bare(sig), errno)
+    }
+
+    /// Accept a signal in this set if one is pending, without blocking.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn poll(&mut self) -> io::Result<Option<SignalInfo>> {
+        let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+        let timeout = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        let sig = unsafe { libc::sigtimedwait(&self.0, &mut info, &timeout) };
+        if sig < 0 {
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() == Some(libc::EAGAIN) {
+                return Ok(None);
+            }
+            return Err(err);
+        }
+        Ok(Some(SignalInfo::from_siginfo(sig, &info)))
+    }
+
+    /// Wait for a signal in this set until `deadline`, and return it along
+    /// with its `siginfo_t`, or `None` if `deadline` passed first.
+    ///
+    /// If `deadline` has already passed, this only accepts a signal that is
+    /// already pending. If the wait is interrupted, it is resumed with the
+    /// time that is left until `deadline`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn wait_until(
+        &mut self,
+        deadline: Instant,
+    ) -> io::Result<Option<SignalInfo>> {
+        loop {
+            let now = Instant::now();
+            let left = if deadline > now {
+                deadline - now
+            } else {
+                Duration::from_secs(0)
+            };
+            let timeout = libc::timespec {
+                tv_sec: cmp::min(left.as_secs(), libc::time_t::MAX as u64)
+                    as libc::time_t,
+                tv_nsec: left.subsec_nanos() as libc::c_long,
+            };
+            let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+            let sig = unsafe {
+                libc::sigtimedwait(&self.0, &mut info, &timeout)
+            };
+            if sig >= 0 {
+                return Ok(Some(SignalInfo::from_siginfo(sig, &info)));
+            }
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                Some(libc::EAGAIN) => return Ok(None),
+                Some(libc::EINTR) => continue,
+                _ => return Err(err),
+            }
+        }
+    }
+
+    /// Accept a signal in this set if one is pending, without blocking.
+    /// There's no `sigtimedwait` on this platform, so this only waits on
+    /// the signals that `sigpending` reports.
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn poll(&mut self) -> io::Result<Option<SignalInfo>> {
+        match self.pending_in() {
+            None => Ok(None),
+            Some(mut pending) => pending.wait().map(Some),
+        }
+    }
+
+    fn thread_block_signals(&self) -> io::Result<()> {
+        let ecode = unsafe {
+            pthread_sigmask(SIG_BLOCK, &self.0, ptr::null_mut())
+        };
+        mask_errno((), ecode)
+    }
+
+    fn thread_unblock_signals(&self) -> io::Result<()> {
+        let ecode = unsafe {
+            pthread_sigmask(SIG_UNBLOCK, &self.0, ptr::null_mut())
+        };
+        mask_errno((), ecode)
+    }
+
+    fn thread_set_signal_mask(&self) -> io::Result<()> {
+        let ecode = unsafe {
+            pthread_sigmask(SIG_SETMASK, &self.0, ptr::null_mut())
+        };
+        mask_errno((), ecode)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn signal_fd(&self) -> io::Result<RawFd> {
+        let fd = unsafe { signalfd(-1, &self.0, libc::SFD_CLOEXEC) };
+        if fd < 0 { Err(io::Error::last_os_error()) } else { Ok(fd) }
+    }
+}
+
+/// Set the disposition of `sig` to `handler` (e.g., `SIG_IGN` or `SIG_DFL`).
+fn set_disposition(sig: Sig, handler: libc::sighandler_t) -> io::Result<()> {
+    let mut act: libc::sigaction = unsafe { mem::zeroed() };
+    act.sa_sigaction = handler;
+    if lock(&RESTART).contains(sig as usize) {
+        act.sa_flags = libc::SA_RESTART;
+    }
+    let ecode = unsafe { libc::sigaction(sig, &act, ptr::null_mut()) };
+    if ecode != 0 { Err(io::Error::last_os_error()) } else { Ok(()) }
+}
+
+/// Get the current disposition of `sig`.
+fn get_disposition(sig: Sig) -> io::Result<libc::sigaction> {
+    let mut act: libc::sigaction = unsafe { mem::zeroed() };
+    let ecode = unsafe { libc::sigaction(sig, ptr::null(), &mut act) };
+    if ecode != 0 { Err(io::Error::last_os_error()) } else { Ok(act) }
+}
+
+fn ok_errno<T>(ok: T, ecode: libc::c_int) -> io::Result<T> {
+    if ecode != 0 { Err(io::Error::from_raw_os_error(ecode)) } else { Ok(ok) }
+}
+
+/// Like `ok_errno`, but for `pthread_sigmask`.
+fn mask_errno<T>(ok: T, ecode: libc::c_int) -> io::Result<T> {
+    ok_errno(ok, ecode).map_err(|err| SignalError::MaskFailed(err).into())
+}
+
+extern "C" {
+    #[cfg(any(
+        target_os = "linux", target_os = "redox", target_os = "fuchsia"
+    ))]
+    fn __errno_location() -> *mut libc::c_int;
+    #[cfg(any(
+        target_os = "macos", target_os = "ios",
+        target_os = "freebsd", target_os = "dragonfly"
+    ))]
+    fn __error() -> *mut libc::c_int;
+    #[cfg(any(
+        target_os = "android",
+        target_os = "netbsd", target_os = "openbsd"
+    ))]
+    fn __errno() -> *mut libc::c_int;
+    #[cfg(any(target_os = "solaris", target_os = "illumos"))]
+    fn ___errno() -> *mut libc::c_int;
+    #[cfg(target_os = "haiku")]
+    fn _errnop() -> *mut libc::c_int;
+}
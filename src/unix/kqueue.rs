@@ -0,0 +1,141 @@
+// The watcher thread's backend for the `kqueue` feature, which waits for
+// signals with kqueue's `EVFILT_SIGNAL` filter instead of `sigwait`.
+//
+// kqueue records every attempt to send a signal, along with the number of
+// times it happened since it was last reported, but it doesn't accept the
+// signal. The signals stay blocked, so each one is still accepted with
+// `sigwait` once it's known to be pending. A signal that kqueue reports but
+// that isn't pending (e.g., because it is ignored) is skipped, since
+// `sigwait` would never have returned it either.
+
+use std::cmp;
+use std::io;
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::ptr;
+
+use bit_set::BitSet;
+
+use super::{Sig, SigSet, SignalInfo};
+
+/// Waits for signals with a kqueue.
+pub struct Waiter {
+    kq: RawFd,
+    /// The signals that have been added to the kqueue.
+    registered: BitSet,
+}
+
+impl Waiter {
+    pub fn new() -> io::Result<Waiter> {
+        let kq = unsafe { libc::kqueue() };
+        if kq < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        unsafe {
+            libc::fcntl(kq, libc::F_SETFD, libc::FD_CLOEXEC);
+        }
+        Ok(Waiter { kq: kq, registered: BitSet::new() })
+    }
+
+    /// Wait for a signal in `listen`, and return it along with the number
+    /// of times it was sent since it was last returned.
+    pub fn wait(
+        &mut self,
+        listen: &mut SigSet,
+    ) -> io::Result<(SignalInfo, usize)> {
+        loop {
+            if let Some(received) = self.next(listen, None)? {
+                return Ok(received);
+            }
+        }
+    }
+
+    /// Like `wait`, but returns `None` instead of blocking.
+    pub fn poll(
+        &mut self,
+        listen: &mut SigSet,
+    ) -> io::Result<Option<(SignalInfo, usize)>> {
+        let timeout = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        self.next(listen, Some(&timeout))
+    }
+
+    /// Accept the next pending signal in `listen` that the kqueue reports,
+    /// or return `None` if `timeout` expires first.
+    fn next(
+        &mut self,
+        listen: &SigSet,
+        timeout: Option<&libc::timespec>,
+    ) -> io::Result<Option<(SignalInfo, usize)>> {
+        self.register(listen)?;
+        let timeout = timeout.map_or(ptr::null(), |t| t as *const _);
+        loop {
+            let mut event: libc::kevent = unsafe { mem::zeroed() };
+            let n = unsafe {
+                libc::kevent(self.kq, ptr::null(), 0, &mut event, 1, timeout)
+            };
+            if n < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+            if n == 0 {
+                return Ok(None);
+            }
+            let sig = event.ident as Sig;
+            if !listen.contains(sig) || !SigSet::pending()?.contains(sig) {
+                continue;
+            }
+            let mut only = SigSet::empty();
+            only.add(sig)?;
+            let info = only.wait()?;
+            return Ok(Some((info, cmp::max(event.data as usize, 1))));
+        }
+    }
+
+    /// Add every signal in `listen` to the kqueue that isn't already.
+    ///
+    /// Signals are never removed. One that is no longer in `listen` is
+    /// skipped by `next` instead.
+    fn register(&mut self, listen: &SigSet) -> io::Result<()> {
+        let mut changes = vec![];
+        for signal in listen.signals() {
+            let sig = signal.as_sig();
+            if self.registered.contains(sig as usize) {
+                continue;
+            }
+            let mut change: libc::kevent = unsafe { mem::zeroed() };
+            change.ident = sig as libc::uintptr_t;
+            change.filter = libc::EVFILT_SIGNAL as _;
+            change.flags = libc::EV_ADD as _;
+            changes.push(change);
+        }
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let n = unsafe {
+            libc::kevent(
+                self.kq,
+                changes.as_ptr(), changes.len() as _,
+                ptr::null_mut(), 0,
+                ptr::null(),
+            )
+        };
+        if n < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        for change in &changes {
+            self.registered.insert(change.ident as usize);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Waiter {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.kq);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use futures::task::{self, Task};
+use futures::{Async, Poll, Stream};
+
+use super::{Signal, lock, subscribe_callback, unsubscribe_callback};
+
+/// The most signals a stream buffers before dropping new ones. This matches
+/// the buffer of the channel returned by `notify`.
+const CAPACITY: usize = 100;
+
+/// Create a new stream subscribed to the given signals.
+///
+/// This is like `notify`, except that signals are yielded by a
+/// `futures::Stream` instead of being sent on a channel, so that they can be
+/// consumed from an event loop (e.g., `tokio`) without dedicating a thread
+/// to blocking on `recv`. The task polling the stream is woken up by the
+/// watcher thread whenever a signal arrives.
+///
+/// The stream never ends and never yields an error. Dropping it
+/// unsubscribes it. If it falls behind by more than a small number of
+/// signals, new signals are dropped.
+///
+/// This is only available when the `async` feature is enabled.
+///
+/// **THIS MUST BE CALLED BEFORE ANY OTHER THREADS ARE SPAWNED IN YOUR
+/// PROCESS.** In particular, it must be called before starting a runtime
+/// that spawns worker threads.
+pub fn notify_stream(signals: &[Signal]) -> SignalStream {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State { queue: VecDeque::new(), task: None }),
+    });
+    let callback_shared = shared.clone();
+    let id = subscribe_callback(signals, Box::new(move |sig| {
+        callback_shared.push(sig);
+        true
+    }));
+    SignalStream { id, shared }
+}
+
+/// A stream of signals created by `notify_stream`.
+pub struct SignalStream {
+    id: usize,
+    shared: Arc<Shared>,
+}
+
+impl Stream for SignalStream {
+    type Item = Signal;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<Signal>, ()> {
+        let mut state = lock(&self.shared.state);
+        match state.queue.pop_front() {
+            Some(sig) => Ok(Async::Ready(Some(sig))),
+            None => {
+                state.task = Some(task::current());
+                Ok(Async::NotReady)
+            }
+        }
+    }
+}
+
+impl Drop for SignalStream {
+    fn drop(&mut self) {
+        unsubscribe_callback(self.id);
+    }
+}
+
+/// The state shared between a stream and the watcher thread.
+struct Shared {
+    state: Mutex<State>,
+}
+
+struct State {
+    queue: VecDeque<Signal>,
+    /// The task to wake up when a signal arrives, if it is waiting for one.
+    task: Option<Task>,
+}
+
+impl Shared {
+    fn push(&self, sig: Signal) {
+        let mut state = lock(&self.state);
+        if state.queue.len() < CAPACITY {
+            state.queue.push_back(sig);
+        }
+        if let Some(task) = state.task.take() {
+            task.notify();
+        }
+    }
+}